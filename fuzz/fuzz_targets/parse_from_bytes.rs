@@ -0,0 +1,23 @@
+#![no_main]
+
+use gost_56042::{Dialect, ParserStrategy, Payment, RawRequisite};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = Payment::parser().parse_from_bytes(data);
+    let _ = Payment::requisite_tolerance_parser().parse_from_bytes(data);
+    let _ = Payment::loose_parser().parse_from_bytes(data);
+    let _ = Payment::requisite_tolerance_parser()
+        .with_dialect(Dialect::lenient())
+        .parse_from_bytes(data);
+    let _ = Payment::loose_parser()
+        .with_dialect(Dialect::lenient())
+        .parse_from_bytes(data);
+    let _ = Payment::<RawRequisite>::loose_custom_parser().parse_from_bytes(data);
+
+    // Режим точного воспроизведения должен возвращать исходные байты
+    if let Ok(payment) = Payment::<RawRequisite>::round_trip_custom_parser().parse_from_bytes(data)
+    {
+        assert_eq!(payment.to_bytes().as_deref(), Ok(data));
+    }
+});
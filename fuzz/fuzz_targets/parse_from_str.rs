@@ -0,0 +1,22 @@
+#![no_main]
+
+use gost_56042::{Dialect, ParserStrategy, Payment, RawRequisite};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &str| {
+    let _ = Payment::parser().parse_from_str(data);
+    let _ = Payment::requisite_tolerance_parser().parse_from_str(data);
+    let _ = Payment::loose_parser().parse_from_str(data);
+    let _ = Payment::requisite_tolerance_parser()
+        .with_dialect(Dialect::lenient())
+        .parse_from_str(data);
+    let _ = Payment::loose_parser()
+        .with_dialect(Dialect::lenient())
+        .parse_from_str(data);
+    let _ = Payment::<RawRequisite>::loose_custom_parser().parse_from_str(data);
+
+    // Режим точного воспроизведения должен возвращать исходную строку
+    if let Ok(payment) = Payment::<RawRequisite>::round_trip_custom_parser().parse_from_str(data) {
+        assert_eq!(payment.to_utf8_lossy().as_deref(), Ok(data));
+    }
+});
@@ -0,0 +1,402 @@
+//! Адрес плательщика.
+
+use core::{fmt::Display, str::FromStr};
+
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{CustomRequisites, Payment, Requisite};
+
+/// Виды улиц: сокращение в адресе и варианты записи.
+const STREET_KINDS: [(&str, &[&str]); 11] = [
+    ("ул.", &["улица", "ул"]),
+    ("пр-кт", &["проспект", "просп", "пр-кт", "пр-т"]),
+    ("пер.", &["переулок", "пер"]),
+    ("ш.", &["шоссе", "ш"]),
+    ("б-р", &["бульвар", "б-р", "бул"]),
+    ("пл.", &["площадь", "пл"]),
+    ("наб.", &["набережная", "наб"]),
+    ("проезд", &["проезд", "пр-д"]),
+    ("мкр.", &["микрорайон", "мкр"]),
+    ("туп.", &["тупик", "туп"]),
+    ("аллея", &["аллея"]),
+];
+
+/// Виды субъектов РФ: сокращение, варианты записи и место сокращения после названия.
+const REGION_KINDS: [(&str, &[&str], bool); 5] = [
+    ("обл.", &["область", "обл"], true),
+    ("край", &["край"], true),
+    ("АО", &["автономный округ", "ао"], true),
+    ("Аобл.", &["автономная область", "аобл"], true),
+    ("Респ.", &["республика", "респ"], false),
+];
+
+const CITY_WORDS: [&str; 3] = ["город", "гор", "г"];
+
+const HOUSE_WORDS: [&str; 2] = ["дом", "д"];
+
+const FLAT_WORDS: [&str; 2] = ["квартира", "кв"];
+
+/// Адрес плательщика: регион, город, улица, дом и квартира.
+///
+/// Записывается в реквизит `PayerAddress` в виде `Московская обл., г. Химки, ул. Ленина, д. 1, кв. 15`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Address {
+    region: Option<Box<str>>,
+    city: Option<Box<str>>,
+    street: Option<Box<str>>,
+    house: Option<Box<str>>,
+    flat: Option<Box<str>>,
+}
+
+impl Address {
+    /// Пустой адрес.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Установка региона.
+    ///
+    /// Вид субъекта приводится к сокращению: «Московская область» - `Московская обл.`.
+    pub fn with_region(mut self, region: &str) -> Self {
+        self.region = non_empty(region).map(|region| normalize_region(&region));
+        self
+    }
+
+    /// Установка города без сокращения `г.`.
+    pub fn with_city(mut self, city: &str) -> Self {
+        self.city = non_empty(city);
+        self
+    }
+
+    /// Установка улицы.
+    ///
+    /// Вид улицы приводится к сокращению: «Ленина улица» - `ул. Ленина`.
+    pub fn with_street(mut self, street: &str) -> Self {
+        self.street = non_empty(street).map(|street| normalize_street(&street));
+        self
+    }
+
+    /// Установка номера дома без сокращения `д.`.
+    pub fn with_house(mut self, house: &str) -> Self {
+        self.house = non_empty(house);
+        self
+    }
+
+    /// Установка номера квартиры без сокращения `кв.`.
+    pub fn with_flat(mut self, flat: &str) -> Self {
+        self.flat = non_empty(flat);
+        self
+    }
+
+    /// Регион.
+    pub fn region(&self) -> Option<&str> {
+        self.region.as_deref()
+    }
+
+    /// Город.
+    pub fn city(&self) -> Option<&str> {
+        self.city.as_deref()
+    }
+
+    /// Улица с сокращением вида улицы.
+    pub fn street(&self) -> Option<&str> {
+        self.street.as_deref()
+    }
+
+    /// Номер дома.
+    pub fn house(&self) -> Option<&str> {
+        self.house.as_deref()
+    }
+
+    /// Номер квартиры.
+    pub fn flat(&self) -> Option<&str> {
+        self.flat.as_deref()
+    }
+
+    /// Реквизиты `PayerAddress` и, если есть квартира, `Flat`.
+    pub fn requisites<T: CustomRequisites>(&self) -> Vec<Requisite<T>> {
+        let mut requisites = alloc::vec![Requisite::PayerAddress(self.to_string().into())];
+        requisites.extend(self.flat.clone().map(Requisite::Flat));
+        requisites
+    }
+}
+
+impl FromStr for Address {
+    type Err = super::Error;
+
+    /// Разбор адреса из частей, разделенных запятыми.
+    ///
+    /// Части распознаются по сокращениям: `обл.`, `г.`, `ул.`, `д.`, `кв.` и т.п.
+    /// Часть без сокращения до улицы считается городом, после улицы - номером дома, после дома - квартирой.
+    /// Почтовый индекс в начале адреса пропускается.
+    fn from_str(s: &str) -> super::Result<Self> {
+        let wrong_address = || super::Error::WrongPair("PayerAddress".into(), s.into());
+
+        let mut address = Self::new();
+
+        for (idx, part) in s.split(',').map(normalize).enumerate() {
+            if part.is_empty() || (idx == 0 && part.len() == 6 && is_digits(&part)) {
+                continue;
+            }
+
+            let slot = if let Some(flat) = strip_word(&part, &FLAT_WORDS) {
+                (&mut address.flat, flat)
+            } else if let Some(house) = strip_word(&part, &HOUSE_WORDS) {
+                (&mut address.house, house)
+            } else if let Some(city) = strip_word(&part, &CITY_WORDS) {
+                (&mut address.city, city)
+            } else if street_kind(&part).is_some() {
+                (&mut address.street, part.clone())
+            } else if region_kind(&part).is_some() {
+                (&mut address.region, part.clone())
+            } else if address.street.is_none() && address.house.is_none() {
+                (&mut address.city, part.clone())
+            } else if address.house.is_none() {
+                (&mut address.house, part.clone())
+            } else {
+                (&mut address.flat, part.clone())
+            };
+
+            match slot {
+                (Some(_), _) => return Err(wrong_address()),
+                (_, value) if value.is_empty() => return Err(wrong_address()),
+                (field, value) => *field = Some(value.into()),
+            }
+        }
+
+        address.region = address.region.map(|region| normalize_region(&region));
+        address.street = address.street.map(|street| normalize_street(&street));
+
+        if address == Self::default() {
+            return Err(wrong_address());
+        }
+
+        Ok(address)
+    }
+}
+
+impl Display for Address {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let parts = [
+            self.region.as_deref().map(ToString::to_string),
+            self.city.as_deref().map(|city| format!("г. {}", city)),
+            self.street.as_deref().map(ToString::to_string),
+            self.house.as_deref().map(|house| format!("д. {}", house)),
+            self.flat.as_deref().map(|flat| format!("кв. {}", flat)),
+        ];
+
+        f.write_str(&parts.into_iter().flatten().collect::<Vec<_>>().join(", "))
+    }
+}
+
+impl<T: CustomRequisites> Payment<T> {
+    /// Адрес плательщика из реквизита `PayerAddress`.
+    ///
+    /// Номер квартиры из реквизита `Flat` сверяется с квартирой в адресе,
+    /// а если в адресе квартира не указана, дополняет его.
+    pub fn payer_address(&self) -> super::Result<Option<Address>> {
+        let Some(address) = self.get("PayerAddress") else {
+            return Ok(None);
+        };
+
+        let mut address = address.parse::<Address>()?;
+
+        if let Some(flat) = self.get("Flat").and_then(non_empty) {
+            match &address.flat {
+                Some(address_flat) if *address_flat != flat => {
+                    return Err(super::Error::FlatMismatch {
+                        flat,
+                        address: address_flat.clone(),
+                    })
+                }
+                _ => address.flat = Some(flat),
+            }
+        }
+
+        Ok(Some(address))
+    }
+}
+
+/// Вид улицы: сокращение и название улицы без вида.
+fn street_kind(street: &str) -> Option<(&'static str, String)> {
+    STREET_KINDS.iter().find_map(|(short, words)| {
+        strip_word(street, words)
+            .or_else(|| strip_suffix_word(street, words))
+            .filter(|name| !name.is_empty())
+            .map(|name| (*short, name))
+    })
+}
+
+fn normalize_street(street: &str) -> Box<str> {
+    match street_kind(street) {
+        Some((short, name)) => format!("{} {}", short, name).into(),
+        None => street.into(),
+    }
+}
+
+/// Вид субъекта РФ: сокращение, название без вида и место сокращения.
+fn region_kind(region: &str) -> Option<(&'static str, String, bool)> {
+    REGION_KINDS.iter().find_map(|(short, words, suffix)| {
+        strip_word(region, words)
+            .or_else(|| strip_suffix_word(region, words))
+            .filter(|name| !name.is_empty())
+            .map(|name| (*short, name, *suffix))
+    })
+}
+
+fn normalize_region(region: &str) -> Box<str> {
+    match region_kind(region) {
+        Some((short, name, true)) => format!("{} {}", name, short).into(),
+        Some((short, name, false)) => format!("{} {}", short, name).into(),
+        None => region.into(),
+    }
+}
+
+/// Отделение сокращения в начале части адреса: `ул.Ленина`, `д 1`, `квартира 5`.
+fn strip_word(part: &str, words: &[&str]) -> Option<String> {
+    words.iter().find_map(|word| {
+        let split = part
+            .char_indices()
+            .nth(word.chars().count())
+            .map_or(part.len(), |(idx, _)| idx);
+        let (head, rest) = part.split_at(split);
+
+        if head.to_lowercase() != *word || rest.starts_with(char::is_alphabetic) {
+            return None;
+        }
+
+        Some(rest.trim_start_matches('.').trim().into())
+    })
+}
+
+/// Отделение сокращения в конце части адреса: `Ленина ул.`, `Тверская улица`.
+fn strip_suffix_word(part: &str, words: &[&str]) -> Option<String> {
+    let part = part.trim_end_matches('.');
+
+    words.iter().find_map(|word| {
+        let split = part
+            .char_indices()
+            .rev()
+            .nth(word.chars().count() - 1)
+            .map(|(idx, _)| idx)?;
+        let (rest, tail) = part.split_at(split);
+
+        if tail.to_lowercase() != *word || !rest.ends_with(' ') {
+            return None;
+        }
+
+        Some(rest.trim().into())
+    })
+}
+
+fn is_digits(value: &str) -> bool {
+    value.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn non_empty(value: &str) -> Option<Box<str>> {
+    let value = normalize(value);
+    (!value.is_empty()).then(|| value.into())
+}
+
+fn normalize(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures, Address, Error, Requisite};
+
+    #[test]
+    fn address_test() {
+        let address = Address::new()
+            .with_region("Московская обл.")
+            .with_city("Химки")
+            .with_street("Ленина улица")
+            .with_house("1 корп. 2")
+            .with_flat("15");
+
+        assert_eq!(
+            alloc::format!("{}", address),
+            "Московская обл., г. Химки, ул. Ленина, д. 1 корп. 2, кв. 15"
+        );
+
+        for raw in [
+            "Московская обл., г. Химки, ул. Ленина, д. 1 корп. 2, кв. 15",
+            "141400, Московская область,  г.Химки, улица Ленина, дом 1 корп. 2, квартира 15",
+            "Московская обл, город Химки, Ленина ул., д.1 корп. 2, кв.15",
+        ] {
+            assert_eq!(raw.parse(), Ok(address.clone()));
+        }
+
+        assert_eq!(
+            "Республика Татарстан, г Казань, Баумана ул, д 7".parse(),
+            Ok(Address::new()
+                .with_region("Татарстан Респ.")
+                .with_city("Казань")
+                .with_street("ул Баумана")
+                .with_house("7"))
+        );
+        assert_eq!(
+            "Москва, Ленинский просп., 15, 7".parse(),
+            Ok(Address::new()
+                .with_city("Москва")
+                .with_street("пр-кт Ленинский")
+                .with_house("15")
+                .with_flat("7"))
+        );
+
+        for wrong in [
+            "",
+            "г. Москва, г. Химки",
+            "ул. Ленина, д. 1, кв. 2, 3",
+            "кв.",
+        ] {
+            assert_eq!(
+                wrong.parse::<Address>(),
+                Err(Error::WrongPair("PayerAddress".into(), wrong.into()))
+            );
+        }
+    }
+
+    #[test]
+    fn address_payment_test() {
+        let address = Address::new()
+            .with_city("Москва")
+            .with_street("ул. Тверская")
+            .with_house("7")
+            .with_flat("15");
+
+        let payment = fixtures::payment(address.requisites());
+        assert_eq!(
+            payment.get("PayerAddress"),
+            Some("г. Москва, ул. Тверская, д. 7, кв. 15")
+        );
+        assert_eq!(payment.get("Flat"), Some("15"));
+        assert_eq!(payment.payer_address(), Ok(Some(address.clone())));
+
+        let payment = fixtures::payment([
+            Requisite::PayerAddress("г. Москва, ул. Тверская, д. 7".into()),
+            Requisite::Flat("15".into()),
+        ]);
+        assert_eq!(payment.payer_address(), Ok(Some(address)));
+
+        let payment = fixtures::payment([
+            Requisite::PayerAddress("г. Москва, ул. Тверская, д. 7, кв. 15".into()),
+            Requisite::Flat("16".into()),
+        ]);
+        assert_eq!(
+            payment.payer_address(),
+            Err(Error::FlatMismatch {
+                flat: "16".into(),
+                address: "15".into()
+            })
+        );
+
+        assert_eq!(fixtures::payment([]).payer_address(), Ok(None));
+    }
+}
@@ -0,0 +1,54 @@
+use alloc::{format, string::String};
+
+/// Сумма в копейках из значения реквизита `Sum`.
+pub(crate) fn parse_kopecks(sum: &str) -> Option<u64> {
+    if sum.is_empty() || !sum.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    sum.parse().ok()
+}
+
+/// Сумма в копейках из записи в рублях, например `1000.00`, `1000,5` или `1000`.
+pub(crate) fn parse_rubles(rubles: &str) -> Option<u64> {
+    let (whole, fraction) = match rubles.split_once(['.', ',']) {
+        Some((whole, fraction)) => (whole, fraction),
+        None => (rubles, ""),
+    };
+
+    if fraction.len() > 2 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let whole = parse_kopecks(whole)?;
+    let fraction = match fraction.len() {
+        0 => 0,
+        1 => fraction.parse::<u64>().ok()? * 10,
+        _ => fraction.parse::<u64>().ok()?,
+    };
+
+    whole.checked_mul(100)?.checked_add(fraction)
+}
+
+/// Запись суммы в рублях с двумя знаками после разделителя `decimal`.
+///
+/// Если передан `group`, то разряды рублей разделяются этим символом.
+pub(crate) fn format_rubles(kopecks: u64, decimal: char, group: Option<char>) -> String {
+    let whole = format!("{}", kopecks / 100);
+
+    let whole = match group {
+        Some(group) => {
+            let mut grouped = String::with_capacity(whole.len() * 2);
+            for (idx, digit) in whole.chars().enumerate() {
+                if idx > 0 && (whole.len() - idx) % 3 == 0 {
+                    grouped.push(group);
+                }
+                grouped.push(digit);
+            }
+            grouped
+        }
+        None => whole,
+    };
+
+    format!("{}{}{:02}", whole, decimal, kopecks % 100)
+}
@@ -0,0 +1,499 @@
+//! Справочник БИК Банка России (электронное сообщение ED807).
+
+use alloc::{boxed::Box, collections::BTreeMap, format, string::String, vec::Vec};
+use encoding::Encoding;
+use roxmltree::{Document, Node};
+
+use crate::{CustomRequisites, Payment, RequiredRequisite};
+
+/// Статус участника, исключенного из справочника.
+const PARTICIPANT_DELETED: &str = "PSDL";
+
+/// Статус удаленного счета.
+const ACCOUNT_DELETED: &str = "ACDL";
+
+/// Ограничения участника, означающие прекращение его работы: отзыв (аннулирование) лицензии.
+///
+/// Остальные ограничения, например, ограничение срочных переводов `URRS`, участника не закрывают.
+const CLOSING_RESTRICTIONS: [&str; 1] = ["LWRS"];
+
+/// Типы счетов, которые указываются в реквизите `CorrespAcc`:
+/// корреспондентский счет банка и единый казначейский счет.
+const CORRESP_ACCOUNT_TYPES: [&str; 2] = ["CRSA", "UTRA"];
+
+/// Максимальная длина реквизита `BankName`.
+const BANK_NAME_MAX_CHARS: usize = 45;
+
+/// Организационно-правовые формы, которые не учитываются при сравнении наименований банков.
+const LEGAL_FORMS: [&str; 8] = ["ПАО", "ОАО", "АО", "ЗАО", "ООО", "НКО", "КБ", "АКБ"];
+
+/// Счет участника.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BicAccount {
+    account: Box<str>,
+    kind: Box<str>,
+    active: bool,
+}
+
+impl BicAccount {
+    /// Номер счета.
+    pub fn account(&self) -> &str {
+        &self.account
+    }
+
+    /// Тип счета (`RegulationAccountType`), например `CRSA` - корреспондентский счет.
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// Признак того, что счет не удален и не имеет ограничений.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+}
+
+/// Запись справочника БИК.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BicEntry {
+    bic: Box<str>,
+    name: Box<str>,
+    active: bool,
+    accounts: Vec<BicAccount>,
+}
+
+impl BicEntry {
+    /// БИК.
+    pub fn bic(&self) -> &str {
+        &self.bic
+    }
+
+    /// Наименование участника.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Признак того, что участник не исключен и у него не отозвана лицензия.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Счета участника.
+    pub fn accounts(&self) -> &[BicAccount] {
+        &self.accounts
+    }
+
+    /// Действующие счета, которые указываются в реквизите `CorrespAcc`.
+    pub fn correspondent_accounts(&self) -> impl Iterator<Item = &str> {
+        self.accounts
+            .iter()
+            .filter(|account| account.active && CORRESP_ACCOUNT_TYPES.contains(&&*account.kind))
+            .map(BicAccount::account)
+    }
+
+    /// Значение реквизита `CorrespAcc`: первый действующий корреспондентский счет или `0`, если его нет.
+    pub fn corresp_acc(&self) -> &str {
+        self.correspondent_accounts().next().unwrap_or("0")
+    }
+}
+
+/// Справочник БИК.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BicDirectory {
+    entries: BTreeMap<Box<str>, BicEntry>,
+}
+
+impl BicDirectory {
+    /// Загрузка справочника из файла.
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> super::Result<Self> {
+        let bytes =
+            std::fs::read(path).map_err(|err| super::Error::IoError(format!("{}", err).into()))?;
+
+        Self::from_bytes(&bytes)
+    }
+
+    /// Загрузка справочника из содержимого файла в кодировке Windows-1251 или UTF-8.
+    pub fn from_bytes(bytes: &[u8]) -> super::Result<Self> {
+        let xml = match bytes.strip_prefix(b"\xEF\xBB\xBF") {
+            Some(bytes) => String::from_utf8(bytes.to_vec()).ok(),
+            None if is_win1251(bytes) => encoding::all::WINDOWS_1251
+                .decode(bytes, encoding::DecoderTrap::Strict)
+                .ok(),
+            None => String::from_utf8(bytes.to_vec()).ok(),
+        }
+        .ok_or(super::Error::DecodingError)?;
+
+        Self::from_xml(&xml)
+    }
+
+    /// Загрузка справочника из XML.
+    pub fn from_xml(xml: &str) -> super::Result<Self> {
+        let document = Document::parse(xml)
+            .map_err(|err| super::Error::XmlError(format!("{}", err).into()))?;
+
+        if !document.root_element().has_tag_name("ED807") {
+            return Err(super::Error::MissingElement("ED807".into()));
+        }
+
+        let entries = document
+            .root_element()
+            .children()
+            .filter(|node| node.has_tag_name("BICDirectoryEntry"))
+            .map(read_entry)
+            .map(|entry| entry.map(|entry| (entry.bic.clone(), entry)))
+            .collect::<super::Result<_>>()?;
+
+        Ok(Self { entries })
+    }
+
+    /// Запись справочника по БИК.
+    pub fn get(&self, bic: &str) -> Option<&BicEntry> {
+        self.entries.get(bic)
+    }
+
+    /// Записи справочника в порядке возрастания БИК.
+    pub fn entries(&self) -> impl Iterator<Item = &BicEntry> {
+        self.entries.values()
+    }
+
+    /// Количество записей.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Справочник не содержит записей.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Обязательные реквизиты, в которых наименование банка и корреспондентский счет взяты из справочника.
+    ///
+    /// Наименование участника длиннее 45 символов (например, `ОТДЕЛЕНИЕ ТУЛА БАНКА РОССИИ//УФК по Тульской области г Тула`)
+    /// сокращается до 45 символов по границе слова.
+    pub fn required_requisite(
+        &self,
+        name: &str,
+        personal_acc: &str,
+        bic: &str,
+    ) -> super::Result<RequiredRequisite> {
+        let entry = self.active_entry(bic)?;
+
+        RequiredRequisite::new(
+            name,
+            personal_acc,
+            truncate_name(&entry.name, BANK_NAME_MAX_CHARS),
+            &entry.bic,
+            entry.corresp_acc(),
+        )
+    }
+
+    /// Проверка банковских реквизитов платежа по справочнику.
+    ///
+    /// БИК должен присутствовать в справочнике и быть действующим,
+    /// корреспондентский счет должен совпадать с одним из счетов участника,
+    /// а наименование банка - с наименованием участника без учета регистра, кавычек и организационно-правовой формы.
+    pub fn validate<T: CustomRequisites>(&self, payment: &Payment<T>) -> super::Result<()> {
+        let bic = payment.get_required("BIC")?;
+        let entry = self.active_entry(bic)?;
+
+        let corresp_acc = payment.get_required("CorrespAcc")?;
+        let corresp_acc_matches = match entry.correspondent_accounts().next() {
+            Some(_) => entry
+                .correspondent_accounts()
+                .any(|account| account == corresp_acc),
+            None => corresp_acc == "0" || corresp_acc.is_empty(),
+        };
+
+        if !corresp_acc_matches {
+            return Err(super::Error::BicMismatch {
+                bic: bic.into(),
+                key: "CorrespAcc".into(),
+                passed: corresp_acc.into(),
+                expected: entry.corresp_acc().into(),
+            });
+        }
+
+        let bank_name = payment.get_required("BankName")?;
+        if !is_plausible_name(bank_name, &entry.name) {
+            return Err(super::Error::BicMismatch {
+                bic: bic.into(),
+                key: "BankName".into(),
+                passed: bank_name.into(),
+                expected: entry.name.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn active_entry(&self, bic: &str) -> super::Result<&BicEntry> {
+        let entry = self
+            .get(bic)
+            .ok_or_else(|| super::Error::UnknownBic(bic.into()))?;
+
+        if !entry.active {
+            return Err(super::Error::InactiveBic(bic.into()));
+        }
+
+        Ok(entry)
+    }
+}
+
+fn read_entry(node: Node) -> super::Result<BicEntry> {
+    let bic = node
+        .attribute("BIC")
+        .ok_or_else(|| super::Error::MissingElement("BICDirectoryEntry/@BIC".into()))?;
+
+    let participant = node
+        .children()
+        .find(|child| child.has_tag_name("ParticipantInfo"))
+        .ok_or_else(|| super::Error::MissingElement("BICDirectoryEntry/ParticipantInfo".into()))?;
+
+    let name = participant.attribute("NameP").ok_or_else(|| {
+        super::Error::MissingElement("BICDirectoryEntry/ParticipantInfo/@NameP".into())
+    })?;
+
+    let closed = participant
+        .children()
+        .filter(|child| child.has_tag_name("RstrList"))
+        .filter_map(|restriction| restriction.attribute("Rstr"))
+        .any(|code| CLOSING_RESTRICTIONS.contains(&code));
+
+    let accounts = node
+        .children()
+        .filter(|child| child.has_tag_name("Accounts"))
+        .filter_map(|account| {
+            Some(BicAccount {
+                account: account.attribute("Account")?.into(),
+                kind: account
+                    .attribute("RegulationAccountType")
+                    .unwrap_or_default()
+                    .into(),
+                active: account.attribute("AccountStatus") != Some(ACCOUNT_DELETED)
+                    && !account
+                        .children()
+                        .any(|child| child.has_tag_name("AccRstrList")),
+            })
+        })
+        .collect();
+
+    Ok(BicEntry {
+        bic: bic.into(),
+        name: name.into(),
+        active: participant.attribute("ParticipantStatus") != Some(PARTICIPANT_DELETED) && !closed,
+        accounts,
+    })
+}
+
+/// Объявлена ли в XML кодировка Windows-1251.
+fn is_win1251(bytes: &[u8]) -> bool {
+    let declaration = bytes
+        .strip_prefix(b"<?xml")
+        .and_then(|rest| rest.split(|b| *b == b'>').next())
+        .map(|declaration| String::from_utf8_lossy(declaration).to_ascii_lowercase());
+
+    declaration.is_some_and(|declaration| declaration.contains("windows-1251"))
+}
+
+/// Наименование банка в верхнем регистре без кавычек и организационно-правовой формы.
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '"' | '\'' | '«' | '»' | '“' | '”' | '„' => ' ',
+            c => c,
+        })
+        .collect::<String>()
+        .to_uppercase()
+        .split_whitespace()
+        .filter(|word| !LEGAL_FORMS.contains(word))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Сокращение наименования до `max_chars` символов по границе слова.
+fn truncate_name(name: &str, max_chars: usize) -> &str {
+    let Some((end, _)) = name.char_indices().nth(max_chars) else {
+        return name;
+    };
+
+    let truncated = &name[..end];
+    let truncated = match name[end..].starts_with(char::is_whitespace) {
+        true => truncated,
+        false => truncated
+            .rfind(char::is_whitespace)
+            .map_or(truncated, |idx| &truncated[..idx]),
+    };
+
+    truncated.trim_end()
+}
+
+fn is_plausible_name(passed: &str, expected: &str) -> bool {
+    let passed = normalize_name(passed);
+    let expected = normalize_name(expected);
+
+    !passed.is_empty() && (passed.contains(&expected) || expected.contains(&passed))
+}
+
+#[cfg(test)]
+mod tests {
+    use encoding::Encoding;
+
+    use crate::{fixtures::required, BicDirectory, Error, Payment, RequiredRequisite, StringExt};
+
+    const ED807: &str = r#"<?xml version="1.0" encoding="WINDOWS-1251"?>
+<ED807 xmlns="urn:cbr-ru:ed:v2.0" EDNo="1" EDDate="2024-03-01" CreationReason="FCBD">
+  <BICDirectoryEntry BIC="044525225">
+    <ParticipantInfo NameP="ПАО Сбербанк" PtType="20" ParticipantStatus="PSAC"/>
+    <Accounts Account="30101810400000000225" RegulationAccountType="CRSA" AccountStatus="ACAC"/>
+  </BICDirectoryEntry>
+  <BICDirectoryEntry BIC="017003983">
+    <ParticipantInfo NameP="ОТДЕЛЕНИЕ ТУЛА БАНКА РОССИИ//УФК по Тульской области г Тула" PtType="52" ParticipantStatus="PSAC"/>
+    <Accounts Account="40102810445370000059" RegulationAccountType="UTRA" AccountStatus="ACAC"/>
+  </BICDirectoryEntry>
+  <BICDirectoryEntry BIC="044525000">
+    <ParticipantInfo NameP="ГУ БАНКА РОССИИ ПО ЦФО" PtType="10" ParticipantStatus="PSAC"/>
+  </BICDirectoryEntry>
+  <BICDirectoryEntry BIC="044525222">
+    <ParticipantInfo NameP="АО &quot;ОГРАНИЧЕННЫЙ БАНК&quot;" PtType="20" ParticipantStatus="PSAC">
+      <RstrList Rstr="URRS" RstrDate="2023-05-15"/>
+    </ParticipantInfo>
+    <Accounts Account="30101810000000000333" RegulationAccountType="CRSA" AccountStatus="ACAC">
+      <AccRstrList AccRstr="CLRS" AccRstrDate="2023-05-15"/>
+    </Accounts>
+    <Accounts Account="30101810000000000222" RegulationAccountType="CRSA" AccountStatus="ACAC"/>
+  </BICDirectoryEntry>
+  <BICDirectoryEntry BIC="044525111">
+    <ParticipantInfo NameP="АО &quot;ЗАКРЫТЫЙ БАНК&quot;" PtType="20" ParticipantStatus="PSAC">
+      <RstrList Rstr="LWRS" RstrDate="2023-01-10"/>
+    </ParticipantInfo>
+    <Accounts Account="30101810000000000111" RegulationAccountType="CRSA" AccountStatus="ACDL"/>
+  </BICDirectoryEntry>
+</ED807>"#;
+
+    fn directory() -> BicDirectory {
+        let bytes = encoding::all::WINDOWS_1251
+            .encode(ED807, encoding::EncoderTrap::Strict)
+            .unwrap();
+
+        BicDirectory::from_bytes(&bytes).unwrap()
+    }
+
+    fn payment(bank_name: &str, bic: &str, correstp_acc: &str) -> Payment {
+        Payment::builder(RequiredRequisite {
+            bank_name: bank_name.to_max_size().unwrap(),
+            bic: bic.to_exact_size().unwrap(),
+            correstp_acc: correstp_acc.to_max_size().unwrap(),
+            ..required()
+        })
+        .build()
+    }
+
+    #[test]
+    fn bic_directory_test() {
+        let directory = directory();
+
+        assert_eq!(directory.len(), 5);
+
+        let entry = directory.get("044525225").unwrap();
+        assert_eq!(entry.name(), "ПАО Сбербанк");
+        assert!(entry.is_active());
+        assert_eq!(entry.corresp_acc(), "30101810400000000225");
+
+        assert_eq!(
+            directory.get("017003983").unwrap().corresp_acc(),
+            "40102810445370000059"
+        );
+        assert_eq!(directory.get("044525000").unwrap().corresp_acc(), "0");
+        assert!(!directory.get("044525111").unwrap().is_active());
+
+        let entry = directory.get("044525222").unwrap();
+        assert!(entry.is_active());
+        assert!(!entry.accounts()[0].is_active());
+        assert_eq!(entry.corresp_acc(), "30101810000000000222");
+
+        assert_eq!(BicDirectory::from_xml(ED807), Ok(directory));
+    }
+
+    #[test]
+    fn bic_directory_validate_test() {
+        let directory = directory();
+
+        assert_eq!(
+            directory.validate(&payment("Сбербанк", "044525225", "30101810400000000225")),
+            Ok(())
+        );
+        assert_eq!(
+            directory.validate(&payment("ГУ Банка России по ЦФО", "044525000", "0")),
+            Ok(())
+        );
+        assert_eq!(
+            directory.validate(&payment(
+                "ПАО Сбербанк",
+                "044525225",
+                "30101810000000000111"
+            )),
+            Err(Error::BicMismatch {
+                bic: "044525225".into(),
+                key: "CorrespAcc".into(),
+                passed: "30101810000000000111".into(),
+                expected: "30101810400000000225".into(),
+            })
+        );
+        assert_eq!(
+            directory.validate(&payment("ВТБ", "044525225", "30101810400000000225")),
+            Err(Error::BicMismatch {
+                bic: "044525225".into(),
+                key: "BankName".into(),
+                passed: "ВТБ".into(),
+                expected: "ПАО Сбербанк".into(),
+            })
+        );
+        assert_eq!(
+            directory.validate(&payment(
+                "Ограниченный банк",
+                "044525222",
+                "30101810000000000222"
+            )),
+            Ok(())
+        );
+        assert_eq!(
+            directory.validate(&payment("Банк", "044525999", "0")),
+            Err(Error::UnknownBic("044525999".into()))
+        );
+        assert_eq!(
+            directory.validate(&payment(
+                "Закрытый банк",
+                "044525111",
+                "30101810000000000111"
+            )),
+            Err(Error::InactiveBic("044525111".into()))
+        );
+    }
+
+    #[test]
+    fn bic_directory_required_requisite_test() {
+        let required = directory()
+            .required_requisite("ООО «Три кита»", "40702810138250123017", "044525225")
+            .unwrap();
+
+        assert_eq!(
+            Payment::builder(required).build(),
+            payment("ПАО Сбербанк", "044525225", "30101810400000000225")
+        );
+
+        let required = directory()
+            .required_requisite(
+                "УФК по Тульской области",
+                "03100643000000016600",
+                "017003983",
+            )
+            .unwrap();
+        assert_eq!(
+            &*required.bank_name,
+            "ОТДЕЛЕНИЕ ТУЛА БАНКА РОССИИ//УФК по Тульской"
+        );
+        assert_eq!(&*required.correstp_acc, "40102810445370000059");
+
+        let payment = Payment::builder(required).build();
+        assert_eq!(directory().validate(&payment), Ok(()));
+    }
+}
@@ -0,0 +1,367 @@
+//! Обмен платежными поручениями в формате 1С «Клиент-Банк» (`1CClientBankExchange`).
+//!
+//! Функции работают со строками, перекодирование файла (обычно Windows-1251) выполняется вызывающей стороной.
+
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+
+use crate::{
+    amount::{format_rubles, parse_kopecks, parse_rubles},
+    CustomRequisites, NoCustomRequisites, Payment, RequiredRequisite, Requisite,
+};
+
+const FILE_HEADER: &str = "1CClientBankExchange";
+const FILE_END: &str = "КонецФайла";
+const DOCUMENT_START: &str = "СекцияДокумент=Платежное поручение";
+const DOCUMENT_END: &str = "КонецДокумента";
+const LINE_END: &str = "\r\n";
+
+/// Соответствие полей документа реквизитам, кроме обязательных реквизитов и суммы.
+///
+/// Налоговые поля 101–110 идут в порядке номеров полей платежного поручения.
+const FIELDS: [(&str, &str); 12] = [
+    ("ПлательщикИНН", "PayerINN"),
+    ("ПолучательИНН", "PayeeINN"),
+    ("ПолучательКПП", "KPP"),
+    ("НазначениеПлатежа", "Purpose"),
+    ("СтатусСоставителя", "DrawerStatus"),
+    ("ПоказательКБК", "CBC"),
+    ("ОКАТО", "OKTMO"),
+    ("ПоказательОснования", "PaytReason"),
+    ("ПоказательПериода", "TaxPeriod"),
+    ("ПоказательНомера", "DocNo"),
+    ("ПоказательДаты", "DocDate"),
+    ("ПоказательТипа", "TaxPayKind"),
+];
+
+/// Поля платежного поручения, которых нет среди реквизитов платежа.
+///
+/// 1С требует их в каждой секции документа.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClientBankDocument {
+    /// Номер платежного поручения.
+    pub number: Box<str>,
+
+    /// Дата платежного поручения в формате `ДД.ММ.ГГГГ`.
+    pub date: Box<str>,
+
+    /// Наименование плательщика.
+    pub payer: Box<str>,
+
+    /// Расчетный счет плательщика.
+    pub payer_account: Box<str>,
+}
+
+impl ClientBankDocument {
+    /// Создание полей платежного поручения.
+    pub fn new(number: &str, date: &str, payer: &str, payer_account: &str) -> Self {
+        Self {
+            number: number.into(),
+            date: date.into(),
+            payer: payer.into(),
+            payer_account: payer_account.into(),
+        }
+    }
+}
+
+impl Payment {
+    /// Чтение всех платежных поручений из файла обмена.
+    pub fn from_client_bank_exchange(text: &str) -> super::Result<Vec<Payment>> {
+        Payment::<NoCustomRequisites>::custom_from_client_bank_exchange(text)
+    }
+}
+
+impl<T: CustomRequisites> Payment<T> {
+    /// Преобразование платежа в секцию `СекцияДокумент=Платежное поручение`.
+    ///
+    /// Для преобразования необходим реквизит `Sum`. Номер, дата и плательщик берутся из `document`,
+    /// если какое-либо из этих полей пустое, возвращается ошибка [`crate::Error::MissingElement`].
+    /// Значения с переводом строки не допускаются форматом и возвращают ошибку [`crate::Error::WrongPair`].
+    pub fn to_client_bank_document(&self, document: &ClientBankDocument) -> super::Result<String> {
+        let sum = self.get_required("Sum")?;
+        let kopecks =
+            parse_kopecks(sum).ok_or_else(|| super::Error::WrongPair("Sum".into(), sum.into()))?;
+
+        let mut text = String::with_capacity(512);
+
+        push_line(&mut text, DOCUMENT_START);
+        push_required_field(&mut text, "Номер", &document.number)?;
+        push_required_field(&mut text, "Дата", &document.date)?;
+        push_field(&mut text, "Сумма", &format_rubles(kopecks, '.', None))?;
+        push_required_field(&mut text, "ПлательщикСчет", &document.payer_account)?;
+        push_required_field(&mut text, "Плательщик", &document.payer)?;
+        push_field(&mut text, "Получатель", self.get_required("Name")?)?;
+        push_field(
+            &mut text,
+            "ПолучательСчет",
+            self.get_required("PersonalAcc")?,
+        )?;
+        push_field(&mut text, "ПолучательБанк1", self.get_required("BankName")?)?;
+        push_field(&mut text, "ПолучательБИК", self.get_required("BIC")?)?;
+        push_field(
+            &mut text,
+            "ПолучательКорсчет",
+            self.get_required("CorrespAcc")?,
+        )?;
+
+        for (field, key) in FIELDS {
+            if let Some(value) = self.get(key) {
+                push_field(&mut text, field, value)?;
+            }
+        }
+
+        push_line(&mut text, DOCUMENT_END);
+
+        Ok(text)
+    }
+
+    /// Формирование файла обмена с платежными поручениями.
+    pub fn to_client_bank_exchange<'a>(
+        payments: impl IntoIterator<Item = (&'a Payment<T>, &'a ClientBankDocument)>,
+    ) -> super::Result<String>
+    where
+        T: 'a,
+    {
+        let mut text = String::with_capacity(1024);
+
+        push_line(&mut text, FILE_HEADER);
+        push_field(&mut text, "ВерсияФормата", "1.03")?;
+        push_field(&mut text, "Кодировка", "Windows")?;
+
+        for (payment, document) in payments {
+            text.push_str(&payment.to_client_bank_document(document)?);
+        }
+
+        push_line(&mut text, FILE_END);
+
+        Ok(text)
+    }
+
+    /// Чтение всех платежных поручений с пользовательскими реквизитами из файла обмена.
+    ///
+    /// Секции других видов документов и неизвестные поля пропускаются.
+    pub fn custom_from_client_bank_exchange(text: &str) -> super::Result<Vec<Self>> {
+        let mut lines = text.lines().map(str::trim);
+
+        if lines.next().map(|line| line.trim_start_matches('\u{feff}')) != Some(FILE_HEADER) {
+            return Err(super::Error::MissingElement(FILE_HEADER.into()));
+        }
+
+        let mut payments = Vec::new();
+
+        while let Some(line) = lines.next() {
+            if line != DOCUMENT_START {
+                continue;
+            }
+
+            let fields = lines
+                .by_ref()
+                .take_while(|line| *line != DOCUMENT_END)
+                .filter_map(|line| line.split_once('='))
+                .collect::<Vec<_>>();
+
+            payments.push(Self::from_client_bank_fields(&fields)?);
+        }
+
+        Ok(payments)
+    }
+
+    fn from_client_bank_fields(fields: &[(&str, &str)]) -> super::Result<Self> {
+        let field = |name: &str| {
+            fields
+                .iter()
+                .find(|(field, value)| *field == name && !value.is_empty())
+                .map(|(_, value)| *value)
+        };
+        let required_field =
+            |name: &str| field(name).ok_or_else(|| super::Error::MissingElement(name.into()));
+
+        let name = field("Получатель1")
+            .or_else(|| field("Получатель"))
+            .ok_or_else(|| super::Error::MissingElement("Получатель".into()))?;
+        let personal_acc = required_field("ПолучательСчет")?;
+        let bank_name = field("ПолучательБанк1")
+            .or_else(|| field("ПолучательБанк"))
+            .ok_or_else(|| super::Error::MissingElement("ПолучательБанк1".into()))?;
+        let bic = required_field("ПолучательБИК")?;
+        let correstp_acc = required_field("ПолучательКорсчет")?;
+
+        let required = RequiredRequisite::new(name, personal_acc, bank_name, bic, correstp_acc)?;
+
+        let sum = required_field("Сумма")?;
+        let kopecks =
+            parse_rubles(sum).ok_or_else(|| super::Error::WrongPair("Sum".into(), sum.into()))?;
+
+        let mut requisites = Vec::with_capacity(FIELDS.len() + 1);
+        requisites.push(Requisite::try_from((
+            "Sum",
+            format!("{}", kopecks).as_str(),
+        ))?);
+
+        for (name, key) in FIELDS {
+            if let Some(value) = field(name) {
+                requisites.push(Requisite::try_from((key, value))?);
+            }
+        }
+
+        Ok(Payment::custom_builder(required)
+            .with_additional_requisites(requisites)
+            .build())
+    }
+}
+
+fn push_line(text: &mut String, line: &str) {
+    text.push_str(line);
+    text.push_str(LINE_END);
+}
+
+fn push_field(text: &mut String, field: &str, value: &str) -> super::Result<()> {
+    if value.contains(['\r', '\n']) {
+        return Err(super::Error::WrongPair(field.into(), value.into()));
+    }
+
+    text.push_str(field);
+    text.push('=');
+    text.push_str(value);
+    text.push_str(LINE_END);
+
+    Ok(())
+}
+
+fn push_required_field(text: &mut String, field: &str, value: &str) -> super::Result<()> {
+    if value.trim().is_empty() {
+        return Err(super::Error::MissingElement(field.into()));
+    }
+
+    push_field(text, field, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures, ClientBankDocument, Error, Payment, Requisite, StringExt};
+
+    fn document() -> ClientBankDocument {
+        ClientBankDocument::new(
+            "15",
+            "01.03.2024",
+            "ИП Иванов И. И.",
+            "40802810400000000001",
+        )
+    }
+
+    fn payment() -> Payment {
+        fixtures::payment([
+            Requisite::Sum("100000".to_max_size().unwrap()),
+            Requisite::PayerINN("500100732259".to_max_size().unwrap()),
+            Requisite::PayeeINN("6200098765".to_max_size().unwrap()),
+            Requisite::KPP("620001001".to_max_size().unwrap()),
+            Requisite::Purpose("Оплата членского взноса".to_max_size().unwrap()),
+            Requisite::DrawerStatus("01".to_max_size().unwrap()),
+            Requisite::CBC("18210102010011000110".to_max_size().unwrap()),
+            Requisite::OKTMO("45000000".to_max_size().unwrap()),
+            Requisite::PaytReason("ТП".to_max_size().unwrap()),
+            Requisite::TaxPeriod("МС.03.2024".to_max_size().unwrap()),
+            Requisite::DocNo("0".to_max_size().unwrap()),
+            Requisite::DocDate("0".to_max_size().unwrap()),
+        ])
+    }
+
+    #[test]
+    fn client_bank_document_test() {
+        let document = payment().to_client_bank_document(&document()).unwrap();
+
+        assert!(document.starts_with(
+            "СекцияДокумент=Платежное поручение\r\nНомер=15\r\nДата=01.03.2024\r\nСумма=1000.00\r\n"
+        ));
+        assert!(document
+            .contains("\r\nПлательщикСчет=40802810400000000001\r\nПлательщик=ИП Иванов И. И.\r\n"));
+        assert!(document.contains("\r\nПолучательСчет=40702810138250123017\r\n"));
+        assert!(document.contains("\r\nПоказательКБК=18210102010011000110\r\n"));
+        assert!(document.contains("\r\nОКАТО=45000000\r\n"));
+        assert!(!document.contains("ПоказательТипа"));
+        assert!(document.ends_with("КонецДокумента\r\n"));
+    }
+
+    #[test]
+    fn client_bank_exchange_round_trip_test() {
+        let payments = [payment(), payment()];
+        let document = document();
+        let text =
+            Payment::to_client_bank_exchange(payments.iter().map(|payment| (payment, &document)))
+                .unwrap();
+
+        assert!(text.starts_with("1CClientBankExchange\r\nВерсияФормата=1.03\r\n"));
+        assert!(text.ends_with("КонецФайла\r\n"));
+
+        assert_eq!(
+            Payment::from_client_bank_exchange(&text),
+            Ok(payments.to_vec())
+        );
+    }
+
+    #[test]
+    fn client_bank_exchange_parse_test() {
+        let text = "1CClientBankExchange
+ВерсияФормата=1.03
+Кодировка=Windows
+СекцияРасчСчет
+РасчСчет=40702810138250123017
+КонецРасчСчет
+СекцияДокумент=Платежное поручение
+Номер=15
+Сумма=1234,5
+Получатель=ИНН 6200098765 ООО «Три кита»
+Получатель1=ООО «Три кита»
+ПолучательСчет=40702810138250123017
+ПолучательБанк1=ОАО \"БАНК\"
+ПолучательБИК=044525225
+ПолучательКорсчет=30101810400000000225
+ПоказательТипа=
+КонецДокумента
+СекцияДокумент=Платежное требование
+Сумма=1
+КонецДокумента
+КонецФайла";
+
+        let payments = Payment::from_client_bank_exchange(text).unwrap();
+
+        assert_eq!(payments.len(), 1);
+        assert_eq!(payments[0].get("Name"), Some("ООО «Три кита»"));
+        assert_eq!(payments[0].get("Sum"), Some("123450"));
+        assert_eq!(payments[0].get("TaxPayKind"), None);
+    }
+
+    #[test]
+    fn client_bank_errors_test() {
+        let text = "1CClientBankExchange
+СекцияДокумент=Платежное поручение
+Сумма=10.00
+Получатель=ООО «Три кита»
+КонецДокумента
+КонецФайла";
+
+        assert_eq!(
+            Payment::from_client_bank_exchange(text),
+            Err(Error::MissingElement("ПолучательСчет".into()))
+        );
+        assert_eq!(
+            Payment::from_client_bank_exchange("КонецФайла"),
+            Err(Error::MissingElement("1CClientBankExchange".into()))
+        );
+
+        let mut document = document();
+        document.number = "".into();
+        assert_eq!(
+            payment().to_client_bank_document(&document),
+            Err(Error::MissingElement("Номер".into()))
+        );
+
+        let document = ClientBankDocument::new("15", "01.03.2024", "ИП Иванов\r\nИ. И.", "1");
+        assert_eq!(
+            payment().to_client_bank_document(&document),
+            Err(Error::WrongPair(
+                "Плательщик".into(),
+                "ИП Иванов\r\nИ. И.".into()
+            ))
+        );
+    }
+}
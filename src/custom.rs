@@ -1,3 +1,5 @@
+use alloc::boxed::Box;
+
 /// Трейт необходим для расширения списка реквизитов.
 ///
 /// Лучше всего реализовывать на `enum` типах.
@@ -9,7 +11,7 @@ pub trait CustomRequisites: for<'a> TryFrom<(&'a str, &'a str), Error = super::E
     fn value(&self) -> &str;
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct NoCustomRequisites;
 
 impl CustomRequisites for NoCustomRequisites {
@@ -29,3 +31,51 @@ impl TryFrom<(&str, &str)> for NoCustomRequisites {
         Err(super::Error::UnknownPair(key.into(), value.into()))
     }
 }
+
+/// Реквизит с произвольными ключом и значением.
+///
+/// Позволяет сохранять неизвестные пары без потерь, например, при использовании
+/// `Payment::<RawRequisite>::loose_custom_parser()`.
+///
+/// Ключ не должен содержать `=`, а ключ и значение - разделитель платежа,
+/// иначе запись платежа вернет ошибку [`crate::Error::WrongPair`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RawRequisite {
+    /// Ключ.
+    pub key: Box<str>,
+
+    /// Значение.
+    pub value: Box<str>,
+}
+
+impl RawRequisite {
+    /// Создание реквизита из пары ключ-значение.
+    pub fn new(key: impl Into<Box<str>>, value: impl Into<Box<str>>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+}
+
+impl CustomRequisites for RawRequisite {
+    fn key(&self) -> &str {
+        &self.key
+    }
+
+    fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl TryFrom<(&str, &str)> for RawRequisite {
+    type Error = super::Error;
+
+    fn try_from((key, value): (&str, &str)) -> Result<Self, Self::Error> {
+        if key.contains('=') {
+            return Err(super::Error::WrongPair(key.into(), value.into()));
+        }
+
+        Ok(Self::new(key, value))
+    }
+}
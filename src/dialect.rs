@@ -0,0 +1,287 @@
+//! Диалекты платежей, которые формируют отдельные банки.
+
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{PaymentEncoding, PaymentHeader, Version, FORMAT_ID_BYTES};
+
+/// Распространенные нестандартные названия реквизитов.
+///
+/// Псевдоним не должен совпадать с ключом стандарта: псевдонимы проверяются раньше ключей.
+const COMMON_ALIASES: [(&str, &str); 5] = [
+    ("Summ", "Sum"),
+    ("Summa", "Sum"),
+    ("CorrAcc", "CorrespAcc"),
+    ("Bik", "BIC"),
+    ("Inn", "PayeeINN"),
+];
+
+/// Отклонения от стандарта, которые допускают нестрогие парсеры.
+///
+/// По умолчанию диалект не допускает отклонений.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dialect {
+    case_insensitive_keys: bool,
+    aliases: Vec<(Box<str>, Box<str>)>,
+    lenient_header: bool,
+    default_encoding: PaymentEncoding,
+}
+
+impl Dialect {
+    /// Диалект, покрывающий часто встречающиеся отклонения:
+    /// ключи в любом регистре, распространенные псевдонимы и заголовок без разделителя или кодировки.
+    pub fn lenient() -> Self {
+        COMMON_ALIASES.iter().fold(
+            Self::default()
+                .with_case_insensitive_keys(true)
+                .with_lenient_header(true),
+            |dialect, (alias, key)| dialect.with_alias(*alias, *key),
+        )
+    }
+
+    /// Сравнение ключей без учета регистра.
+    pub fn with_case_insensitive_keys(mut self, enabled: bool) -> Self {
+        self.case_insensitive_keys = enabled;
+        self
+    }
+
+    /// Добавление псевдонима `alias` для ключа `key`.
+    pub fn with_alias(mut self, alias: impl Into<Box<str>>, key: impl Into<Box<str>>) -> Self {
+        self.aliases.push((alias.into(), key.into()));
+        self
+    }
+
+    /// Допуск заголовка без разделителя (`ST00012Name=...`) или без признака кодировки (`ST0001|Name=...`).
+    ///
+    /// Отсутствующий разделитель считается `|`, отсутствующая кодировка задается [`Dialect::with_default_encoding`].
+    pub fn with_lenient_header(mut self, enabled: bool) -> Self {
+        self.lenient_header = enabled;
+        self
+    }
+
+    /// Кодировка для заголовка без признака кодировки.
+    pub fn with_default_encoding(mut self, encoding: PaymentEncoding) -> Self {
+        self.default_encoding = encoding;
+        self
+    }
+
+    /// Ключ стандарта, соответствующий ключу из платежа версии `version`.
+    ///
+    /// Без учета регистра ключ сравнивается с реквизитами версии. Неизвестные ключи возвращаются без изменений.
+    pub fn canonical_key<'a>(&'a self, key: &'a str, version: &Version) -> &'a str {
+        let eq = |a: &str, b: &str| {
+            if self.case_insensitive_keys {
+                a.eq_ignore_ascii_case(b)
+            } else {
+                a == b
+            }
+        };
+
+        if let Some((_, canonical)) = self.aliases.iter().find(|(alias, _)| eq(alias, key)) {
+            return canonical;
+        }
+
+        if self.case_insensitive_keys {
+            if let Some(rule) = version.rules().iter().find(|rule| eq(rule.key, key)) {
+                return rule.key;
+            }
+        }
+
+        key
+    }
+
+    /// Чтение нестандартного заголовка.
+    ///
+    /// Возвращает заголовок и длину заголовка в байтах.
+    pub(crate) fn read_header(&self, bytes: &[u8]) -> Option<(PaymentHeader, usize)> {
+        if !self.lenient_header {
+            return None;
+        }
+
+        let prefix = bytes.get(..7)?;
+        if prefix[..2] != FORMAT_ID_BYTES {
+            return None;
+        }
+
+        let version = [prefix[2], prefix[3], prefix[4], prefix[5]];
+
+        match PaymentEncoding::try_from(prefix[6]) {
+            // Нет разделителя: сразу за кодировкой идет ключ первого реквизита
+            Ok(encoding) if bytes.get(7).is_none_or(u8::is_ascii_alphabetic) => {
+                PaymentHeader::new(version, encoding, '|')
+                    .ok()
+                    .map(|header| (header, 7))
+            }
+            Ok(_) => None,
+            // Нет ни кодировки, ни разделителя
+            Err(_) if prefix[6].is_ascii_alphabetic() => {
+                PaymentHeader::new(version, self.default_encoding, '|')
+                    .ok()
+                    .map(|header| (header, 6))
+            }
+            // Нет кодировки
+            Err(_) => PaymentHeader::new(version, self.default_encoding, prefix[6] as char)
+                .ok()
+                .map(|header| (header, 7)),
+        }
+    }
+}
+
+impl Default for Dialect {
+    fn default() -> Self {
+        Self {
+            case_insensitive_keys: false,
+            aliases: Vec::new(),
+            lenient_header: false,
+            default_encoding: PaymentEncoding::Utf8,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::COMMON_ALIASES;
+    use crate::{
+        Dialect, Error, LengthLimit, ParserStrategy, Payment, PaymentEncoding, PaymentHeader,
+        RawRequisite, Requisite, RequisiteRule, StringExt, Version, VersionRegistry,
+    };
+
+    const BODY: &str = "Name=ООО «Три кита»|personalacc=40702810138250123017|BankName=ОАО \"БАНК\"|Bik=044525225|CorrAcc=30101810400000000225|Summ=100000|PayeeInn=6200098765";
+
+    #[test]
+    fn canonical_key_test() {
+        let dialect = Dialect::lenient().with_alias("Naznachenie", "Purpose");
+
+        assert_eq!(dialect.canonical_key("sum", &Version::V0001), "Sum");
+        assert_eq!(
+            dialect.canonical_key("PAYEEINN", &Version::V0001),
+            "PayeeINN"
+        );
+        assert_eq!(dialect.canonical_key("summ", &Version::V0001), "Sum");
+        assert_eq!(
+            dialect.canonical_key("naznachenie", &Version::V0001),
+            "Purpose"
+        );
+        assert_eq!(dialect.canonical_key("XCustom", &Version::V0001), "XCustom");
+
+        assert_eq!(
+            Dialect::default().canonical_key("sum", &Version::V0001),
+            "sum"
+        );
+    }
+
+    #[test]
+    fn registered_version_keys_test() {
+        const V0002: Version = Version::new(
+            *b"0002",
+            &[
+                RequisiteRule::new("Name", LengthLimit::Max(160)),
+                RequisiteRule::new("PersonalAcc", LengthLimit::Exact(20)),
+                RequisiteRule::new("BankName", LengthLimit::Max(45)),
+                RequisiteRule::new("BIC", LengthLimit::Exact(9)),
+                RequisiteRule::new("CorrespAcc", LengthLimit::Max(20)),
+                RequisiteRule::new("XRegion", LengthLimit::Max(2)),
+            ],
+        );
+
+        let dialect = Dialect::lenient();
+        assert_eq!(dialect.canonical_key("xregion", &V0002), "XRegion");
+        assert_eq!(dialect.canonical_key("xregion", &Version::V0001), "xregion");
+
+        let raw = "ST00022|Name=ООО «Три кита»|personalacc=40702810138250123017|BankName=ОАО \"БАНК\"|BIC=044525225|CorrespAcc=30101810400000000225|xregion=77";
+        let payment = Payment::<RawRequisite>::requisite_tolerance_custom_parser()
+            .with_versions(VersionRegistry::default().with_version(V0002))
+            .with_dialect(dialect)
+            .parse_from_str(raw)
+            .unwrap();
+
+        assert_eq!(payment.get("PersonalAcc"), Some("40702810138250123017"));
+        assert_eq!(payment.get("XRegion"), Some("77"));
+    }
+
+    #[test]
+    fn common_aliases_test() {
+        let rules = Version::V0001.rules();
+
+        for (alias, key) in COMMON_ALIASES {
+            assert!(
+                !rules
+                    .iter()
+                    .any(|rule| rule.key.eq_ignore_ascii_case(alias)),
+                "псевдоним {} совпадает с ключом стандарта",
+                alias
+            );
+            assert!(
+                rules.iter().any(|rule| rule.key == key),
+                "псевдоним {} ссылается на неизвестный ключ {}",
+                alias,
+                key
+            );
+        }
+
+        let dialect = Dialect::lenient();
+        assert_eq!(
+            dialect.canonical_key("PersonalAccount", &Version::V0001),
+            "PersonalAccount"
+        );
+        assert_eq!(
+            dialect.canonical_key("personalaccount", &Version::V0001),
+            "PersonalAccount"
+        );
+    }
+
+    #[test]
+    fn dialect_parser_test() {
+        let expected = [
+            ("Sum", "100000"),
+            ("PayeeINN", "6200098765"),
+            ("PersonalAcc", "40702810138250123017"),
+        ];
+
+        for raw in [
+            alloc::format!("ST00012|{}", BODY),
+            alloc::format!("ST00012{}", BODY),
+            alloc::format!("ST0001|{}", BODY),
+            alloc::format!("ST0001{}", BODY),
+        ] {
+            let payment = Payment::requisite_tolerance_parser()
+                .with_dialect(Dialect::lenient())
+                .parse_from_str(&raw)
+                .unwrap();
+
+            assert_eq!(
+                payment.header(),
+                &PaymentHeader::new(*b"0001", PaymentEncoding::Utf8, '|').unwrap()
+            );
+            for (key, value) in expected {
+                assert_eq!(payment.get(key), Some(value));
+            }
+
+            let payment = Payment::loose_parser()
+                .with_dialect(Dialect::lenient())
+                .parse_from_bytes(raw.as_bytes())
+                .unwrap();
+            assert!(payment
+                .requisites()
+                .any(|r| r == &Requisite::Sum("100000".to_max_size().unwrap())));
+        }
+    }
+
+    #[test]
+    fn strict_parser_ignores_dialect_test() {
+        let raw = alloc::format!("ST00012{}", BODY);
+
+        assert!(Payment::parser().parse_from_str(&raw).is_err());
+        assert!(Payment::requisite_tolerance_parser()
+            .parse_from_str(&raw)
+            .is_err());
+
+        let raw = alloc::format!("ST00012|{}", BODY);
+        assert_eq!(
+            Payment::requisite_tolerance_parser().parse_from_str(&raw),
+            Err(Error::WrongRequiredRequisiteOrder {
+                passed: "BankName".into(),
+                expected: "PersonalAcc".into(),
+            })
+        );
+    }
+}
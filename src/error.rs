@@ -1,12 +1,20 @@
 use core::fmt::{self, Display};
 
-use alloc::boxed::Box;
+use alloc::{boxed::Box, string::String};
 
 pub type Result<T> = core::result::Result<T, Error>;
 
 /// Ошибки при создании платежа и парсинге.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Error {
+    /// Реквизит не совпадает с данными справочника БИК.
+    BicMismatch {
+        bic: Box<str>,
+        key: Box<str>,
+        passed: Box<str>,
+        expected: Box<str>,
+    },
+
     /// Ошибка при парсинге заголовка.
     CorruptedHeader(Box<str>),
 
@@ -16,20 +24,50 @@ pub enum Error {
     /// Ошибка при кодировании тела.
     EncodingError,
 
+    /// Номер квартиры в `Flat` не совпадает с квартирой в адресе плательщика.
+    FlatMismatch { flat: Box<str>, address: Box<str> },
+
+    /// БИК исключен из справочника или участник ограничен в расчетах.
+    InactiveBic(Box<str>),
+
+    /// Ошибка ввода-вывода.
+    IoError(Box<str>),
+
+    /// Отсутствует необходимый элемент документа.
+    MissingElement(Box<str>),
+
+    /// Отсутствует реквизит, необходимый для операции.
+    MissingRequisite(Box<str>),
+
+    /// Ошибка при формировании PDF документа.
+    PdfError(Box<str>),
+
     /// Обязательные реквизиты не предоставлены.
     RequiredRequisiteNotPresented,
 
+    /// Символ реквизита не может быть записан в кодировке платежа.
+    UnencodableChar { key: Box<str>, ch: char },
+
     /// Неизвестная пара реквизитов.
     UnknownPair(Box<str>, Box<str>),
 
+    /// БИК отсутствует в справочнике.
+    UnknownBic(Box<str>),
+
     /// Неизвестный код для кодировки.
     UnknownEncodingCode(u8),
 
     /// Неизвестный технический код платежа.
     UnknownTechCode(Box<str>),
 
+    /// Реквизит не поддерживается версией стандарта.
+    UnsupportedRequisite { key: Box<str>, version: [u8; 4] },
+
     /// Неподдерживаемая версия.
-    UnsupportedVersion { passed: [u8; 4], current: [u8; 4] },
+    UnsupportedVersion {
+        passed: [u8; 4],
+        supported: Box<[[u8; 4]]>,
+    },
 
     /// Неправильный Format ID.
     WrongFormatId([u8; 2]),
@@ -37,44 +75,105 @@ pub enum Error {
     /// Неправильное значение для пары-значения.
     WrongPair(Box<str>, Box<str>),
 
+    /// Строка не является ссылкой СБП.
+    WrongSbpLink(Box<str>),
+
+    /// Казначейский счет получателя указан без единого казначейского счета или наоборот.
+    WrongTreasuryAccounts {
+        personal_acc: Box<str>,
+        corresp_acc: Box<str>,
+    },
+
     /// Неправильный порядок обязательных реквизитов.
     WrongRequiredRequisiteOrder {
         passed: Box<str>,
         expected: Box<str>,
     },
+
+    /// Ошибка при разборе XML документа.
+    XmlError(Box<str>),
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            Error::BicMismatch {
+                bic,
+                key,
+                passed,
+                expected,
+            } => write!(
+                f,
+                "Реквизит {}={} не совпадает со справочником БИК для {}, ожидалось {}",
+                key, passed, bic, expected
+            ),
             Error::CorruptedHeader(err) => write!(f, "Ошибка при парсинге заголовка: \"{}\"", err),
             Error::DecodingError => write!(f, "Ошибка при декодировании тела"),
             Error::EncodingError => write!(f, "Ошибка при кодировании тела"),
+            Error::FlatMismatch { flat, address } => write!(
+                f,
+                "Квартира Flat={} не совпадает с квартирой {} в адресе плательщика",
+                flat, address
+            ),
+            Error::InactiveBic(bic) => write!(f, "БИК {} недействителен", bic),
+            Error::IoError(err) => write!(f, "Ошибка ввода-вывода: {}", err),
+            Error::MissingElement(path) => write!(f, "Отсутствует элемент {}", path),
+            Error::MissingRequisite(key) => write!(f, "Отсутствует реквизит {}", key),
+            Error::PdfError(err) => write!(f, "Ошибка при формировании PDF: {}", err),
             Error::RequiredRequisiteNotPresented => {
                 write!(f, "Обязательные реквизиты не предоставлены")
             }
+            Error::UnencodableChar { key, ch } => write!(
+                f,
+                "Символ {:?} (U+{:04X}) реквизита {} не может быть записан в кодировке платежа",
+                ch, *ch as u32, key
+            ),
             Error::UnknownPair(key, val) => write!(f, "Неизвестный реквизит: {}={}", key, val),
+            Error::UnknownBic(bic) => write!(f, "БИК {} отсутствует в справочнике", bic),
             Error::UnknownEncodingCode(code) => write!(f, "Неизвестный код кодировки {}", code),
             Error::UnknownTechCode(code) => {
                 write!(f, "Неизвестный технический код платежа {}", code)
             }
-            Error::UnsupportedVersion { passed, current } => write!(
+            Error::UnsupportedRequisite { key, version } => write!(
                 f,
-                "Версия {} не поддерживается, текущая версия {}",
-                core::str::from_utf8(passed).unwrap(),
-                core::str::from_utf8(current).unwrap(),
+                "Реквизит {} не поддерживается версией {}",
+                key,
+                String::from_utf8_lossy(version),
             ),
+            Error::UnsupportedVersion { passed, supported } => {
+                write!(
+                    f,
+                    "Версия {} не поддерживается, поддерживаемые версии:",
+                    String::from_utf8_lossy(passed),
+                )?;
+
+                for version in supported.iter() {
+                    write!(f, " {}", String::from_utf8_lossy(version))?;
+                }
+
+                Ok(())
+            }
             Error::WrongFormatId(format_id) => write!(
                 f,
                 "Неправильный Format ID {}{}",
                 format_id[0] as char, format_id[1] as char
             ),
             Error::WrongPair(key, val) => write!(f, "Неправильное значение пары {}={}", key, val),
+            Error::WrongSbpLink(link) => write!(f, "Неправильная ссылка СБП: {}", link),
+            Error::WrongTreasuryAccounts {
+                personal_acc,
+                corresp_acc,
+            } => write!(
+                f,
+                "Казначейскому счету соответствует единый казначейский счет: PersonalAcc={}, CorrespAcc={}",
+                personal_acc, corresp_acc
+            ),
             Error::WrongRequiredRequisiteOrder { passed, expected } => write!(
                 f,
                 "Неправильный порядок обязательных реквизитов. Ожидалось {} встречено {}",
                 expected, passed
             ),
+            Error::XmlError(err) => write!(f, "Ошибка при разборе XML: {}", err),
         }
     }
 }
@@ -0,0 +1,36 @@
+//! Общие данные для тестов.
+
+use crate::{NoCustomRequisites, Payment, RequiredRequisite, Requisite};
+
+/// Требуемые реквизиты получателя с расчетным счетом в коммерческом банке.
+pub(crate) fn required() -> RequiredRequisite {
+    RequiredRequisite::new(
+        "ООО «Три кита»",
+        "40702810138250123017",
+        "ОАО \"БАНК\"",
+        "044525225",
+        "30101810400000000225",
+    )
+    .unwrap()
+}
+
+/// Требуемые реквизиты получателя с казначейским счетом.
+pub(crate) fn treasury_required() -> RequiredRequisite {
+    RequiredRequisite::new(
+        "УФК по г. Москве",
+        "03100643000000017300",
+        "ГУ Банка России по ЦФО//УФК по г. Москве",
+        "004525988",
+        "40102810545370000003",
+    )
+    .unwrap()
+}
+
+/// Платеж с реквизитами [`required`] и дополнительными реквизитами.
+pub(crate) fn payment(
+    requisites: impl IntoIterator<Item = Requisite<NoCustomRequisites>>,
+) -> Payment {
+    Payment::builder(required())
+        .with_additional_requisites(requisites)
+        .build()
+}
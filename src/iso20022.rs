@@ -0,0 +1,369 @@
+//! Преобразование платежа в элемент `CdtTrfTxInf` сообщения ISO 20022 `pain.001` и обратно.
+//!
+//! Формируется только фрагмент с данными перевода: заголовок `GrpHdr` и блок `PmtInf`
+//! с данными плательщика в платеже отсутствуют, поэтому документ `pain.001` собирает вызывающая сторона.
+
+use alloc::{format, string::String, vec::Vec};
+
+use roxmltree::{Document, Node};
+
+use crate::{
+    amount::{format_rubles, parse_kopecks, parse_rubles},
+    CustomRequisites, Payment, RequiredRequisite, Requisite,
+};
+
+/// Код клиринговой системы Банка России, в которой участник идентифицируется по БИК.
+const CLEARING_SYSTEM_CODE: &str = "RUCBC";
+
+/// Идентификатор, используемый когда `DocNo` отсутствует.
+const NOT_PROVIDED: &str = "NOTPROVIDED";
+
+/// Максимальная длина элемента `Ustrd`.
+const USTRD_MAX_CHARS: usize = 140;
+
+/// Максимальная длина реквизита `DocNo`.
+const DOC_NO_MAX_CHARS: usize = 15;
+
+impl Payment {
+    /// Создание платежа из первого элемента `CdtTrfTxInf` в XML.
+    ///
+    /// Принимается как отдельный элемент, так и документ `pain.001` целиком.
+    /// `EndToEndId` длиннее 15 символов не переносится в `DocNo`.
+    pub fn from_pain001(xml: &str) -> super::Result<Self> {
+        Self::custom_from_pain001(xml)
+    }
+}
+
+impl<T: CustomRequisites> Payment<T> {
+    /// Преобразование платежа в элемент `CdtTrfTxInf` сообщения `pain.001`.
+    ///
+    /// Результат является фрагментом, а не документом: банк принимает его только внутри `PmtInf`
+    /// документа `Document/CstmrCdtTrfInitn` с заголовком `GrpHdr`.
+    /// Для преобразования необходим реквизит `Sum`.
+    /// Назначение платежа длиннее 140 символов разбивается на несколько элементов `Ustrd`.
+    pub fn to_pain001_transaction(&self) -> super::Result<String> {
+        let sum = self.get_required("Sum")?;
+        let kopecks =
+            parse_kopecks(sum).ok_or_else(|| super::Error::WrongPair("Sum".into(), sum.into()))?;
+
+        let mut xml = String::with_capacity(1024);
+
+        xml.push_str("<CdtTrfTxInf>");
+
+        xml.push_str("<PmtId><EndToEndId>");
+        push_escaped(&mut xml, self.get("DocNo").unwrap_or(NOT_PROVIDED));
+        xml.push_str("</EndToEndId></PmtId>");
+
+        xml.push_str("<Amt><InstdAmt Ccy=\"RUB\">");
+        xml.push_str(&format_rubles(kopecks, '.', None));
+        xml.push_str("</InstdAmt></Amt>");
+
+        xml.push_str("<CdtrAgt><FinInstnId><ClrSysMmbId><ClrSysId><Cd>");
+        xml.push_str(CLEARING_SYSTEM_CODE);
+        xml.push_str("</Cd></ClrSysId><MmbId>");
+        push_escaped(&mut xml, self.get_required("BIC")?);
+        xml.push_str("</MmbId></ClrSysMmbId><Nm>");
+        push_escaped(&mut xml, self.get_required("BankName")?);
+        xml.push_str("</Nm></FinInstnId></CdtrAgt>");
+
+        xml.push_str("<CdtrAgtAcct><Id><Othr><Id>");
+        push_escaped(&mut xml, self.get_required("CorrespAcc")?);
+        xml.push_str("</Id></Othr></Id></CdtrAgtAcct>");
+
+        xml.push_str("<Cdtr><Nm>");
+        push_escaped(&mut xml, self.get_required("Name")?);
+        xml.push_str("</Nm></Cdtr>");
+
+        xml.push_str("<CdtrAcct><Id><Othr><Id>");
+        push_escaped(&mut xml, self.get_required("PersonalAcc")?);
+        xml.push_str("</Id></Othr></Id></CdtrAcct>");
+
+        let purpose = self.get("Purpose");
+        let payee_inn = self.get("PayeeINN");
+        let kpp = self.get("KPP");
+        let payer_inn = self.get("PayerINN");
+
+        if purpose.is_some() || payee_inn.is_some() || kpp.is_some() || payer_inn.is_some() {
+            xml.push_str("<RmtInf>");
+
+            for part in purpose
+                .into_iter()
+                .flat_map(|val| split_chars(val, USTRD_MAX_CHARS))
+            {
+                xml.push_str("<Ustrd>");
+                push_escaped(&mut xml, part);
+                xml.push_str("</Ustrd>");
+            }
+
+            if payee_inn.is_some() || kpp.is_some() || payer_inn.is_some() {
+                xml.push_str("<Strd><TaxRmt>");
+
+                if payee_inn.is_some() || kpp.is_some() {
+                    xml.push_str("<Cdtr>");
+                    push_element(&mut xml, "TaxId", payee_inn);
+                    push_element(&mut xml, "RegnId", kpp);
+                    xml.push_str("</Cdtr>");
+                }
+
+                if payer_inn.is_some() {
+                    xml.push_str("<Dbtr>");
+                    push_element(&mut xml, "TaxId", payer_inn);
+                    xml.push_str("</Dbtr>");
+                }
+
+                xml.push_str("</TaxRmt></Strd>");
+            }
+
+            xml.push_str("</RmtInf>");
+        }
+
+        xml.push_str("</CdtTrfTxInf>");
+
+        Ok(xml)
+    }
+
+    /// Создание платежа с пользовательскими реквизитами из первого элемента `CdtTrfTxInf` в XML.
+    pub fn custom_from_pain001(xml: &str) -> super::Result<Self> {
+        let document = Document::parse(xml)
+            .map_err(|err| super::Error::XmlError(format!("{}", err).into()))?;
+
+        let tx = document
+            .descendants()
+            .find(|node| node.has_tag_name("CdtTrfTxInf"))
+            .ok_or_else(|| super::Error::MissingElement("CdtTrfTxInf".into()))?;
+
+        let amount = tx
+            .children()
+            .find(|node| node.has_tag_name("Amt"))
+            .and_then(|node| node.children().find(|node| node.has_tag_name("InstdAmt")))
+            .ok_or_else(|| super::Error::MissingElement("Amt/InstdAmt".into()))?;
+
+        let currency = amount.attribute("Ccy").unwrap_or_default();
+        if currency != "RUB" {
+            return Err(super::Error::WrongPair("Ccy".into(), currency.into()));
+        }
+
+        let amount = amount.text().unwrap_or_default().trim();
+        let kopecks = parse_rubles(amount)
+            .ok_or_else(|| super::Error::WrongPair("Sum".into(), amount.into()))?;
+
+        let required = RequiredRequisite::new(
+            required_text(tx, &["Cdtr", "Nm"])?,
+            required_text(tx, &["CdtrAcct", "Id", "Othr", "Id"])?,
+            required_text(tx, &["CdtrAgt", "FinInstnId", "Nm"])?,
+            required_text(tx, &["CdtrAgt", "FinInstnId", "ClrSysMmbId", "MmbId"])?,
+            required_text(tx, &["CdtrAgtAcct", "Id", "Othr", "Id"])?,
+        )?;
+
+        let mut requisites = Vec::with_capacity(5);
+        requisites.push(Requisite::try_from((
+            "Sum",
+            format!("{}", kopecks).as_str(),
+        ))?);
+
+        // Назначение платежа может быть разбито на несколько элементов `Ustrd`
+        let purpose = tx
+            .children()
+            .filter(|node| node.has_tag_name("RmtInf"))
+            .flat_map(|node| node.children().filter(|node| node.has_tag_name("Ustrd")))
+            .filter_map(|node| node.text())
+            .collect::<String>();
+        if !purpose.trim().is_empty() {
+            requisites.push(Requisite::try_from(("Purpose", purpose.trim()))?);
+        }
+
+        let optional = [
+            (
+                "PayeeINN",
+                &["RmtInf", "Strd", "TaxRmt", "Cdtr", "TaxId"][..],
+            ),
+            ("KPP", &["RmtInf", "Strd", "TaxRmt", "Cdtr", "RegnId"][..]),
+            (
+                "PayerINN",
+                &["RmtInf", "Strd", "TaxRmt", "Dbtr", "TaxId"][..],
+            ),
+        ];
+
+        for (key, path) in optional {
+            if let Some(value) = text(tx, path) {
+                requisites.push(Requisite::try_from((key, value))?);
+            }
+        }
+
+        if let Some(doc_no) = text(tx, &["PmtId", "EndToEndId"])
+            .filter(|id| *id != NOT_PROVIDED && id.chars().count() <= DOC_NO_MAX_CHARS)
+        {
+            requisites.push(Requisite::try_from(("DocNo", doc_no))?);
+        }
+
+        Ok(Payment::custom_builder(required)
+            .with_additional_requisites(requisites)
+            .build())
+    }
+}
+
+fn push_element(xml: &mut String, tag: &str, value: Option<&str>) {
+    if let Some(value) = value {
+        xml.push('<');
+        xml.push_str(tag);
+        xml.push('>');
+        push_escaped(xml, value);
+        xml.push_str("</");
+        xml.push_str(tag);
+        xml.push('>');
+    }
+}
+
+/// Разбиение строки на части не длиннее `max_chars` символов.
+fn split_chars(value: &str, max_chars: usize) -> impl Iterator<Item = &str> {
+    let mut rest = value;
+
+    core::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+
+        let end = rest
+            .char_indices()
+            .nth(max_chars)
+            .map_or(rest.len(), |(idx, _)| idx);
+        let (part, tail) = rest.split_at(end);
+        rest = tail;
+
+        Some(part)
+    })
+}
+
+fn push_escaped(xml: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '&' => xml.push_str("&amp;"),
+            '<' => xml.push_str("&lt;"),
+            '>' => xml.push_str("&gt;"),
+            '"' => xml.push_str("&quot;"),
+            '\'' => xml.push_str("&apos;"),
+            c => xml.push(c),
+        }
+    }
+}
+
+fn text<'a>(node: Node<'a, '_>, path: &[&str]) -> Option<&'a str> {
+    path.iter()
+        .try_fold(node, |node, tag| {
+            node.children().find(|child| child.has_tag_name(*tag))
+        })
+        .and_then(|node| node.text())
+        .map(str::trim)
+}
+
+fn required_text<'a>(node: Node<'a, '_>, path: &[&str]) -> super::Result<&'a str> {
+    text(node, path).ok_or_else(|| super::Error::MissingElement(path.join("/").into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use crate::{fixtures::required, Error, Payment, RequiredRequisite, Requisite, StringExt};
+
+    fn payment() -> Payment {
+        payment_with_purpose("Оплата <членского> взноса")
+    }
+
+    fn payment_with_purpose(purpose: &str) -> Payment {
+        Payment::builder(RequiredRequisite {
+            name: "ООО «Три кита» & Co".to_max_size().unwrap(),
+            ..required()
+        })
+        .with_additional_requisites([
+            Requisite::Sum("100050".to_max_size().unwrap()),
+            Requisite::Purpose(purpose.to_max_size().unwrap()),
+            Requisite::PayeeINN("6200098765".to_max_size().unwrap()),
+            Requisite::KPP("620001001".to_max_size().unwrap()),
+            Requisite::PayerINN("500100732259".to_max_size().unwrap()),
+            Requisite::DocNo("42".to_max_size().unwrap()),
+        ])
+        .build()
+    }
+
+    #[test]
+    fn pain001_round_trip_test() {
+        let xml = payment().to_pain001_transaction().unwrap();
+
+        assert!(xml.contains("<InstdAmt Ccy=\"RUB\">1000.50</InstdAmt>"));
+        assert!(xml.contains("<Nm>ООО «Три кита» &amp; Co</Nm>"));
+        assert!(xml.contains("<TaxId>6200098765</TaxId><RegnId>620001001</RegnId>"));
+
+        let parsed = Payment::from_pain001(&xml).unwrap();
+
+        assert_eq!(parsed.get("Sum"), Some("100050"));
+        assert_eq!(parsed.get("Purpose"), Some("Оплата <членского> взноса"));
+        assert_eq!(parsed.get("PayeeINN"), Some("6200098765"));
+        assert_eq!(parsed.get("KPP"), Some("620001001"));
+        assert_eq!(parsed.get("PayerINN"), Some("500100732259"));
+        assert_eq!(parsed.get("DocNo"), Some("42"));
+        assert_eq!(parsed.get("Name"), Some("ООО «Три кита» & Co"));
+        assert_eq!(parsed.get("CorrespAcc"), Some("30101810400000000225"));
+    }
+
+    #[test]
+    fn pain001_long_values_test() {
+        let purpose = "Оплата по договору № 15 от 01.02.2024 ".repeat(5);
+        let xml = payment_with_purpose(purpose.trim())
+            .to_pain001_transaction()
+            .unwrap();
+        assert_eq!(xml.matches("<Ustrd>").count(), 2);
+        assert_eq!(
+            Payment::from_pain001(&xml).unwrap().get("Purpose"),
+            Some(purpose.trim())
+        );
+
+        let xml = xml.replace(
+            "<EndToEndId>42</EndToEndId>",
+            "<EndToEndId>E2E-2024-02-01-000042</EndToEndId>",
+        );
+        let parsed = Payment::from_pain001(&xml).unwrap();
+        assert_eq!(parsed.get("DocNo"), None);
+        assert_eq!(parsed.get("Sum"), Some("100050"));
+    }
+
+    #[test]
+    fn pain001_document_test() {
+        let xml = format!(
+            "<Document xmlns=\"urn:iso:std:iso:20022:tech:xsd:pain.001.001.09\"><CstmrCdtTrfInitn><PmtInf>{}</PmtInf></CstmrCdtTrfInitn></Document>",
+            payment().to_pain001_transaction().unwrap()
+        );
+
+        assert_eq!(
+            Payment::from_pain001(&xml).unwrap().get("BIC"),
+            Some("044525225")
+        );
+    }
+
+    #[test]
+    fn pain001_errors_test() {
+        let payment = Payment::builder(required()).build();
+
+        assert_eq!(
+            payment.to_pain001_transaction(),
+            Err(Error::MissingRequisite("Sum".into()))
+        );
+
+        let xml = "<CdtTrfTxInf><Amt><InstdAmt Ccy=\"RUB\">10.00</InstdAmt></Amt></CdtTrfTxInf>";
+        assert_eq!(
+            Payment::from_pain001(xml),
+            Err(Error::MissingElement("Cdtr/Nm".into()))
+        );
+
+        let xml = "<CdtTrfTxInf><Amt><InstdAmt Ccy=\"EUR\">10.00</InstdAmt></Amt></CdtTrfTxInf>";
+        assert_eq!(
+            Payment::from_pain001(xml),
+            Err(Error::WrongPair("Ccy".into(), "EUR".into()))
+        );
+
+        assert!(matches!(
+            Payment::from_pain001("<CdtTrfTxInf>"),
+            Err(Error::XmlError(_))
+        ));
+    }
+}
@@ -1,26 +1,63 @@
 #![no_std]
 
 extern crate alloc;
-
+#[cfg(feature = "std")]
+extern crate std;
+
+mod address;
+mod amount;
+#[cfg(feature = "bic-directory")]
+mod bic_directory;
+mod client_bank;
 mod custom;
+mod dialect;
 mod error;
+#[cfg(test)]
+mod fixtures;
+#[cfg(feature = "iso20022")]
+mod iso20022;
 mod parser;
+mod payer;
 mod payment;
+#[cfg(feature = "pdf")]
+mod pdf;
+mod profile;
+mod receipt;
+mod sanitize;
+mod sbp;
 mod string_types;
-
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+mod treasury;
+mod version;
+
+pub use address::*;
+#[cfg(feature = "bic-directory")]
+pub use bic_directory::*;
+pub use client_bank::*;
 pub use custom::*;
+pub use dialect::*;
 pub use error::{Error, Result};
 pub use parser::*;
+pub use payer::*;
 pub use payment::*;
+pub use profile::*;
+pub use receipt::*;
+pub use sanitize::*;
+pub use sbp::*;
 pub use string_types::*;
+pub use version::*;
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        string_types::StringExt, CustomRequisites, Error, ParserStrategy, Payment,
-        RequiredRequisite, Requisite,
+        string_types::StringExt, CustomRequisites, Error, LengthLimit, LengthUnit, ParserStrategy,
+        Payment, PaymentEncoding, PaymentHeader, RawRequisite, RequiredRequisite, Requisite,
+        RequisiteRule, RequisiteSize, Version, VersionRegistry,
     };
 
+    use alloc::{string::ToString, vec::Vec};
+
     #[test]
     fn encoding_test() {
         let raw = "ST00012|Name=ООО «Три кита»|PersonalAcc=40702810138250123017|BankName=ОАО \"БАНК\"|BIC=044525225|CorrespAcc=30101810400000000225";
@@ -233,4 +270,417 @@ mod tests {
 
         assert_eq!(parsed_payment.unwrap().get("Name"), Some("ООО «Три кита»"));
     }
+
+    #[test]
+    fn raw_requisite_loose_parser_test() {
+        let raw = "ST00012|Name=ООО «Три кита»|PersonalAcc=40702810138250123017|BankName=ОАО \"БАНК\"|BIC=044525225|CorrespAcc=30101810400000000225|Тест=42|Sum=100000|Foo=a=b";
+
+        let parsed_payment = Payment::<RawRequisite>::loose_custom_parser()
+            .parse_from_str(raw)
+            .unwrap();
+
+        assert_eq!(parsed_payment.get("Тест"), Some("42"));
+        assert_eq!(parsed_payment.get("Foo"), Some("a=b"));
+
+        let payment = parsed_payment.to_utf8_lossy();
+        let payment = payment.as_ref().map(|s| s.as_str());
+
+        assert_eq!(payment, Ok(raw));
+    }
+
+    #[test]
+    fn raw_requisite_builder_test() {
+        let raw = "ST00012|Name=ООО «Три кита»|PersonalAcc=40702810138250123017|BankName=ОАО \"БАНК\"|BIC=044525225|CorrespAcc=30101810400000000225|Тест=42";
+
+        let payment = Payment::custom_builder(RequiredRequisite {
+            name: "ООО «Три кита»".to_max_size().unwrap(),
+            personal_acc: "40702810138250123017".to_exact_size().unwrap(),
+            bank_name: "ОАО \"БАНК\"".to_max_size().unwrap(),
+            bic: "044525225".to_exact_size().unwrap(),
+            correstp_acc: "30101810400000000225".to_max_size().unwrap(),
+        })
+        .with_additional_requisites([Requisite::Custom(RawRequisite::new("Тест", "42"))])
+        .build();
+
+        assert_eq!(payment.to_utf8_lossy().as_deref(), Ok(raw));
+        assert_eq!(Payment::custom_parser().parse_from_str(raw), Ok(payment));
+    }
+
+    #[test]
+    fn raw_requisite_separator_test() {
+        assert_eq!(
+            RawRequisite::try_from(("Тест=1", "42")),
+            Err(Error::WrongPair("Тест=1".into(), "42".into()))
+        );
+
+        let required = RequiredRequisite {
+            name: "ООО «Три кита»".to_max_size().unwrap(),
+            personal_acc: "40702810138250123017".to_exact_size().unwrap(),
+            bank_name: "ОАО \"БАНК\"".to_max_size().unwrap(),
+            bic: "044525225".to_exact_size().unwrap(),
+            correstp_acc: "30101810400000000225".to_max_size().unwrap(),
+        };
+
+        for (key, value) in [("Тест=1", "42"), ("Тест|1", "42"), ("Тест", "4|2")] {
+            let payment = Payment::custom_builder(required.clone())
+                .with_additional_requisites([Requisite::Custom(RawRequisite::new(key, value))])
+                .build();
+
+            assert_eq!(
+                payment.to_bytes(),
+                Err(Error::WrongPair(key.into(), value.into()))
+            );
+        }
+
+        let payment = Payment::builder(required)
+            .with_additional_requisites([Requisite::Purpose("Оплата|услуг".to_max_size().unwrap())])
+            .build();
+        assert_eq!(
+            payment.to_bytes(),
+            Err(Error::WrongPair("Purpose".into(), "Оплата|услуг".into()))
+        );
+    }
+
+    #[test]
+    fn round_trip_parser_test() {
+        let raw = "ST00012|Name=ООО «Три кита»||Тест=42|fasfdsfsdfs|PersonalAcc=123|  |Sum=100000|";
+
+        let parsed_payment = Payment::round_trip_parser().parse_from_str(raw).unwrap();
+
+        assert_eq!(parsed_payment.get("Name"), Some("ООО «Три кита»"));
+        assert_eq!(parsed_payment.get("Sum"), Some("100000"));
+        assert_eq!(
+            parsed_payment.unparsed_segments().collect::<Vec<_>>(),
+            ["", "Тест=42", "fasfdsfsdfs", "PersonalAcc=123", "  ", ""]
+        );
+        assert_eq!(parsed_payment.to_string(), raw);
+
+        let payment = parsed_payment.to_utf8_lossy();
+        let payment = payment.as_ref().map(|s| s.as_str());
+
+        assert_eq!(payment, Ok(raw));
+    }
+
+    #[test]
+    fn round_trip_parser_edit_test() {
+        let raw = "ST00012|Name=ООО «Три кита»||Тест=42|Sum=100000|fasfdsfsdfs|Purpose=Оплата";
+
+        let mut payment = Payment::round_trip_parser().parse_from_str(raw).unwrap();
+        payment.requisites.remove(1);
+        payment
+            .requisites
+            .push(Requisite::PayeeINN("7707089101".to_max_size().unwrap()));
+
+        assert_eq!(
+            payment.to_string(),
+            "ST00012|Name=ООО «Три кита»||Тест=42|Purpose=Оплата|fasfdsfsdfs|PayeeINN=7707089101"
+        );
+
+        payment.requisites.truncate(1);
+        assert_eq!(
+            payment.to_string(),
+            "ST00012|Name=ООО «Три кита»||Тест=42|fasfdsfsdfs"
+        );
+    }
+
+    #[test]
+    fn round_trip_parser_bytes_test() {
+        let payment = Payment::builder(RequiredRequisite {
+            name: "ООО «Три кита»".to_max_size().unwrap(),
+            personal_acc: "40702810138250123017".to_exact_size().unwrap(),
+            bank_name: "ОАО \"БАНК\"".to_max_size().unwrap(),
+            bic: "044525225".to_exact_size().unwrap(),
+            correstp_acc: "30101810400000000225".to_max_size().unwrap(),
+        })
+        .with_encdoing(PaymentEncoding::Win1251)
+        .build();
+
+        let mut raw = payment.to_bytes().unwrap();
+        raw.extend(b"|\xd2\xe5\xf1\xf2|TechCode=1|");
+
+        let parsed_payment = Payment::round_trip_parser().parse_from_bytes(&raw).unwrap();
+
+        assert_eq!(parsed_payment.get("BIC"), Some("044525225"));
+        assert_eq!(
+            parsed_payment.unparsed_segments().collect::<Vec<_>>(),
+            ["Тест", "TechCode=1", ""]
+        );
+        assert_eq!(parsed_payment.to_bytes(), Ok(raw));
+
+        assert_eq!(
+            Payment::round_trip_parser().parse_from_bytes(b"ST00012|Name=\xff"),
+            Err(Error::DecodingError)
+        );
+    }
+
+    #[test]
+    fn version_registry_test() {
+        const V0002: Version = Version::new(
+            *b"0002",
+            &[
+                RequisiteRule::new("Name", LengthLimit::Max(10)),
+                RequisiteRule::new("PersonalAcc", LengthLimit::Exact(20)),
+                RequisiteRule::new("BankName", LengthLimit::Max(45)),
+                RequisiteRule::new("BIC", LengthLimit::Exact(9)),
+                RequisiteRule::new("CorrespAcc", LengthLimit::Max(20)),
+            ],
+        );
+
+        let parser =
+            Payment::parser().with_versions(VersionRegistry::default().with_version(V0002));
+
+        let raw = "ST00012|Name=ООО «Три кита»|PersonalAcc=40702810138250123017|BankName=ОАО \"БАНК\"|BIC=044525225|CorrespAcc=30101810400000000225|Sum=100000";
+        assert!(parser.parse_from_str(raw).is_ok());
+
+        let raw = "ST00022|Name=ООО «Три кита»|PersonalAcc=40702810138250123017|BankName=ОАО \"БАНК\"|BIC=044525225|CorrespAcc=30101810400000000225";
+        assert_eq!(
+            parser.parse_from_str(raw),
+            Err(Error::WrongPair("Name".into(), "ООО «Три кита»".into()))
+        );
+
+        let raw = "ST00022|Name=Три кита|PersonalAcc=40702810138250123017|BankName=ОАО \"БАНК\"|BIC=044525225|CorrespAcc=30101810400000000225|Sum=100000";
+        assert_eq!(
+            parser.parse_from_str(raw),
+            Err(Error::UnsupportedRequisite {
+                key: "Sum".into(),
+                version: *b"0002"
+            })
+        );
+
+        let parsed_payment = Payment::requisite_tolerance_parser()
+            .with_versions(VersionRegistry::default().with_version(V0002))
+            .parse_from_str(raw)
+            .unwrap();
+        assert_eq!(parsed_payment.get("Sum"), None);
+
+        let parsed_payment = Payment::loose_parser()
+            .with_versions(VersionRegistry::default().with_version(V0002))
+            .parse_from_str(raw)
+            .unwrap();
+        assert_eq!(parsed_payment.get("Sum"), None);
+
+        let parsed_payment = Payment::<RawRequisite>::round_trip_custom_parser()
+            .with_versions(VersionRegistry::default().with_version(V0002))
+            .parse_from_str(raw)
+            .unwrap();
+        assert_eq!(parsed_payment.get("Sum"), None);
+        assert_eq!(parsed_payment.to_utf8_lossy().as_deref(), Ok(raw));
+
+        let v0002_parser = Payment::parser()
+            .with_versions(VersionRegistry::default().with_version(V0002))
+            .with_version(*b"0002")
+            .unwrap();
+        assert!(v0002_parser
+            .parse_from_str(raw.trim_end_matches("|Sum=100000"))
+            .is_ok());
+        assert_eq!(
+            Payment::parser()
+                .with_version(*b"0001")
+                .unwrap()
+                .parse_from_str(raw),
+            Err(Error::UnsupportedVersion {
+                passed: *b"0002",
+                supported: [*b"0001"].into()
+            })
+        );
+
+        let raw = "ST00032|Name=Три кита";
+        assert_eq!(
+            parser.parse_from_str(raw),
+            Err(Error::UnsupportedVersion {
+                passed: *b"0003",
+                supported: [*b"0001", *b"0002"].into()
+            })
+        );
+    }
+
+    #[test]
+    fn length_unit_test() {
+        const V0002: Version = Version::new(
+            *b"0002",
+            &[
+                RequisiteRule::new("Name", LengthLimit::Max(20)),
+                RequisiteRule::new("PersonalAcc", LengthLimit::Exact(20)),
+                RequisiteRule::new("BankName", LengthLimit::Max(45)),
+                RequisiteRule::new("BIC", LengthLimit::Exact(9)),
+                RequisiteRule::new("CorrespAcc", LengthLimit::Max(20)),
+            ],
+        );
+
+        let builder = || {
+            Payment::builder(RequiredRequisite {
+                name: "ООО «Три кита»".to_max_size().unwrap(),
+                personal_acc: "40702810138250123017".to_exact_size().unwrap(),
+                bank_name: "ОАО \"БАНК\"".to_max_size().unwrap(),
+                bic: "044525225".to_exact_size().unwrap(),
+                correstp_acc: "30101810400000000225".to_max_size().unwrap(),
+            })
+            .with_version_spec(V0002)
+        };
+
+        let payment = builder().try_build().unwrap();
+        assert_eq!(
+            payment.requisite_sizes()[..2],
+            [
+                RequisiteSize {
+                    key: "Name",
+                    chars: 14,
+                    bytes: Some(26)
+                },
+                RequisiteSize {
+                    key: "PersonalAcc",
+                    chars: 20,
+                    bytes: Some(20)
+                },
+            ]
+        );
+
+        assert_eq!(
+            builder()
+                .with_length_unit(LengthUnit::EncodedBytes)
+                .try_build(),
+            Err(Error::WrongPair("Name".into(), "ООО «Три кита»".into()))
+        );
+        assert!(builder()
+            .with_encdoing(PaymentEncoding::Win1251)
+            .with_length_unit(LengthUnit::EncodedBytes)
+            .try_build()
+            .is_ok());
+        assert_eq!(
+            builder()
+                .with_encdoing(PaymentEncoding::Koi8R)
+                .with_length_unit(LengthUnit::EncodedBytes)
+                .try_build(),
+            Err(Error::UnencodableChar {
+                key: "Name".into(),
+                ch: '«'
+            })
+        );
+
+        let raw = payment.to_bytes().unwrap();
+        let parser =
+            || Payment::parser().with_versions(VersionRegistry::default().with_version(V0002));
+        assert_eq!(parser().parse_from_bytes(&raw), Ok(payment));
+        assert_eq!(
+            parser()
+                .with_length_unit(LengthUnit::EncodedBytes)
+                .parse_from_bytes(&raw),
+            Err(Error::WrongPair("Name".into(), "ООО «Три кита»".into()))
+        );
+
+        let parsed_payment = Payment::loose_parser()
+            .with_versions(VersionRegistry::default().with_version(V0002))
+            .with_length_unit(LengthUnit::EncodedBytes)
+            .parse_from_bytes(&raw)
+            .unwrap();
+        assert_eq!(parsed_payment.get("Name"), None);
+
+        let parsed_payment = Payment::<RawRequisite>::round_trip_custom_parser()
+            .with_versions(VersionRegistry::default().with_version(V0002))
+            .with_length_unit(LengthUnit::EncodedBytes)
+            .parse_from_bytes(&raw)
+            .unwrap();
+        assert_eq!(parsed_payment.get("Name"), None);
+        assert_eq!(parsed_payment.to_bytes(), Ok(raw));
+    }
+
+    #[test]
+    fn parser_unknown_version_test() {
+        assert_eq!(
+            Payment::parser().with_version(*b"0002").map(|_| ()),
+            Err(Error::UnsupportedVersion {
+                passed: *b"0002",
+                supported: [*b"0001"].into()
+            })
+        );
+    }
+
+    #[test]
+    fn builder_unknown_version_test() {
+        let builder = Payment::builder(RequiredRequisite {
+            name: "ООО «Три кита»".to_max_size().unwrap(),
+            personal_acc: "40702810138250123017".to_exact_size().unwrap(),
+            bank_name: "ОАО \"БАНК\"".to_max_size().unwrap(),
+            bic: "044525225".to_exact_size().unwrap(),
+            correstp_acc: "30101810400000000225".to_max_size().unwrap(),
+        });
+
+        assert_eq!(
+            builder.with_version(*b"0002").map(|_| ()),
+            Err(Error::UnsupportedVersion {
+                passed: *b"0002",
+                supported: [*b"0001"].into()
+            })
+        );
+    }
+
+    #[test]
+    fn header_test() {
+        let header = PaymentHeader::new(*b"0001", PaymentEncoding::Koi8R, ';').unwrap();
+
+        assert_eq!(&header.to_bytes(), b"ST00013;");
+        assert_eq!(
+            PaymentHeader::try_from(&header.to_bytes()[..]),
+            Ok(header.clone())
+        );
+        assert_eq!("ST00013;".parse::<PaymentHeader>(), Ok(header));
+
+        assert_eq!(
+            "SS00012|".parse::<PaymentHeader>(),
+            Err(Error::WrongFormatId(*b"SS"))
+        );
+        assert_eq!(
+            "ST00019|".parse::<PaymentHeader>(),
+            Err(Error::UnknownEncodingCode(b'9'))
+        );
+        assert!("ST00012".parse::<PaymentHeader>().is_err());
+        assert!("ST0x012|".parse::<PaymentHeader>().is_err());
+        assert!("ST00012=".parse::<PaymentHeader>().is_err());
+        assert!(PaymentHeader::new(*b"0001", PaymentEncoding::Utf8, '№').is_err());
+
+        for separator in ['a', '1', ' ', '\n', '\0'] {
+            assert_eq!(
+                PaymentHeader::new(*b"0001", PaymentEncoding::Utf8, separator),
+                Err(Error::CorruptedHeader(
+                    alloc::format!("Недопустимый разделитель {:?}", separator).into()
+                ))
+            );
+        }
+        assert!("ST00012a".parse::<PaymentHeader>().is_err());
+    }
+
+    #[test]
+    fn header_mut_test() {
+        let mut payment = Payment::builder(RequiredRequisite {
+            name: "ООО «Три кита»".to_max_size().unwrap(),
+            personal_acc: "40702810138250123017".to_exact_size().unwrap(),
+            bank_name: "ОАО \"БАНК\"".to_max_size().unwrap(),
+            bic: "044525225".to_exact_size().unwrap(),
+            correstp_acc: "30101810400000000225".to_max_size().unwrap(),
+        })
+        .build();
+
+        payment.header_mut().set_encoding(PaymentEncoding::Win1251);
+        payment.header_mut().set_separator('#').unwrap();
+
+        let bytes = payment.to_bytes().unwrap();
+
+        assert_eq!(
+            PaymentHeader::try_from(&bytes[..8]).as_ref(),
+            Ok(payment.header())
+        );
+        assert_eq!(Payment::parser().parse_from_bytes(&bytes), Ok(payment));
+    }
+
+    #[test]
+    fn multibyte_header_test() {
+        let raw = "ST0001Ф|Name=ООО «Три кита»";
+        assert!(Payment::loose_parser().parse_from_str(raw).is_err());
+
+        let raw = "ST00012Ф|Name=ООО «Три кита»";
+        assert!(Payment::loose_parser().parse_from_str(raw).is_err());
+
+        let raw = "ST00\u{1F600}";
+        assert!(Payment::loose_parser().parse_from_str(raw).is_err());
+    }
 }
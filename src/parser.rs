@@ -8,8 +8,8 @@ use alloc::{
 use encoding::Encoding;
 
 use crate::{
-    CustomRequisites, NoCustomRequisites, Payment, PaymentEncoding, PaymentHeader, Requisite,
-    FORMAT_ID_BYTES, VERSION_0001_BYTES,
+    CustomRequisites, Dialect, LengthUnit, NoCustomRequisites, PayerIdType, Payment,
+    PaymentEncoding, PaymentHeader, Phone, RawSegment, Requisite, Snils, Version, VersionRegistry,
 };
 
 /// Интерфейс для парсеров.
@@ -29,38 +29,77 @@ pub struct PaymentParser<
     T: ParserStrategyType = StrictParser,
     RT: CustomRequisites = NoCustomRequisites,
 > {
-    version_id: [u8; 4],
+    versions: VersionRegistry,
+    dialect: Dialect,
+    length_unit: LengthUnit,
     _req_marker: PhantomData<RT>,
     _marker: PhantomData<T>,
 }
 
 impl<T: ParserStrategyType, RT: CustomRequisites> PaymentParser<T, RT> {
-    /// Установка версии.
-    pub fn with_version(mut self, version_id: [u8; 4]) -> Self {
-        self.version_id = version_id;
+    /// Ограничение поддерживаемых версий одной версией.
+    ///
+    /// Версия ищется в наборе версий парсера, а затем среди известных библиотеке версий.
+    /// Если версия не найдена, возвращается ошибка [`crate::Error::UnsupportedVersion`].
+    pub fn with_version(mut self, version_id: [u8; 4]) -> super::Result<Self> {
+        let known = VersionRegistry::default();
+
+        let Some(version) = self.versions.get(version_id).or(known.get(version_id)) else {
+            let supported = self
+                .versions
+                .ids()
+                .chain(known.ids().filter(|id| self.versions.get(*id).is_none()))
+                .collect::<Vec<_>>();
+
+            return Err(super::Error::UnsupportedVersion {
+                passed: version_id,
+                supported: supported.into(),
+            });
+        };
+
+        self.versions = VersionRegistry::empty().with_version(*version);
+        Ok(self)
+    }
+
+    /// Установка набора поддерживаемых версий.
+    pub fn with_versions(mut self, versions: VersionRegistry) -> Self {
+        self.versions = versions;
+        self
+    }
+
+    /// Единица измерения длины реквизитов при проверке по правилам версии.
+    ///
+    /// Длина в байтах измеряется для кодировки из заголовка платежа.
+    /// Учитывается всеми парсерами так же, как правила версии.
+    pub fn with_length_unit(mut self, length_unit: LengthUnit) -> Self {
+        self.length_unit = length_unit;
         self
     }
 }
 
 impl<RT: CustomRequisites> ParserStrategy<RT> for PaymentParser<StrictParser, RT> {
     fn parse_from_str(&self, val: &str) -> crate::Result<Payment<RT>> {
-        let header = self.read_payment_header(val, true)?;
+        let (header, data) = self.read_payment_header(val, true)?;
 
-        let data = val[8..].to_string();
-
-        let requisites = self.read_requisites(&data, header.separator as char)?;
+        let requisites = self.read_requisites(data, header.separator as char)?;
 
         self.validate_required_requisites(&requisites)?;
+        self.validate_version_requisites(&header, &requisites)?;
+        self.validate_requisite_formats(&requisites)?;
 
-        Ok(Payment { header, requisites })
+        Ok(Payment {
+            header,
+            requisites,
+            layout: Vec::new(),
+        })
     }
 
     fn parse_from_bytes(&self, bytes: &[u8]) -> crate::Result<Payment<RT>> {
-        let header = self.read_payment_header_bytes(bytes)?;
+        let (header, body) = self.read_payment_header_bytes(bytes)?;
 
         let data = self.decode_payment_body(
             header.encoding,
-            &bytes[8..],
+            body,
             encoding::DecoderTrap::Strict,
             |val| String::from_utf8(val.to_vec()).map_err(|_| super::Error::DecodingError),
         )?;
@@ -68,8 +107,14 @@ impl<RT: CustomRequisites> ParserStrategy<RT> for PaymentParser<StrictParser, RT
         let requisites = self.read_requisites(&data, header.separator as char)?;
 
         self.validate_required_requisites(&requisites)?;
+        self.validate_version_requisites(&header, &requisites)?;
+        self.validate_requisite_formats(&requisites)?;
 
-        Ok(Payment { header, requisites })
+        Ok(Payment {
+            header,
+            requisites,
+            layout: Vec::new(),
+        })
     }
 }
 
@@ -82,124 +127,229 @@ impl<RT: CustomRequisites> PaymentParser<StrictParser, RT> {
             .flat_map(|kv| kv.map(|kv| kv.try_into()))
             .collect()
     }
+
+    /// Проверка формата реквизитов, значения которых хранятся как есть.
+    fn validate_requisite_formats(&self, requisites: &[Requisite<RT>]) -> super::Result<()> {
+        requisites.iter().try_for_each(|requisite| match requisite {
+            Requisite::Phone(phone) => phone.parse::<Phone>().map(drop),
+            Requisite::PensAcc(snils) => snils.parse::<Snils>().map(drop),
+            Requisite::PayerIdType(id_type) => id_type.parse::<PayerIdType>().map(drop),
+            _ => Ok(()),
+        })
+    }
 }
 
 impl<RT: CustomRequisites> ParserStrategy<RT> for PaymentParser<RequisiteToleranceParser, RT> {
     fn parse_from_str(&self, val: &str) -> crate::Result<Payment<RT>> {
-        let header = self.read_payment_header(val, true)?;
-
-        let data = val[8..].to_string();
+        let (header, data) = self.read_payment_header(val, true)?;
 
-        let requisites = self.read_requisites(&data, header.separator as char);
+        let mut requisites = self.read_requisites(data, &header);
+        self.retain_version_requisites(&header, &mut requisites);
 
         self.validate_required_requisites(&requisites)?;
 
-        Ok(Payment { header, requisites })
+        Ok(Payment {
+            header,
+            requisites,
+            layout: Vec::new(),
+        })
     }
 
     fn parse_from_bytes(&self, bytes: &[u8]) -> crate::Result<Payment<RT>> {
-        let header = self.read_payment_header_bytes(bytes)?;
+        let (header, body) = self.read_payment_header_bytes(bytes)?;
 
         let data = self.decode_payment_body(
             header.encoding,
-            &bytes[8..],
+            body,
             encoding::DecoderTrap::Strict,
             |val| String::from_utf8(val.to_vec()).map_err(|_| super::Error::DecodingError),
         )?;
 
-        let requisites = self.read_requisites(&data, header.separator as char);
+        let mut requisites = self.read_requisites(&data, &header);
+        self.retain_version_requisites(&header, &mut requisites);
 
         self.validate_required_requisites(&requisites)?;
 
-        Ok(Payment { header, requisites })
+        Ok(Payment {
+            header,
+            requisites,
+            layout: Vec::new(),
+        })
     }
 }
 
 impl<RT: CustomRequisites> PaymentParser<RequisiteToleranceParser, RT> {
-    fn read_requisites(&self, data: &str, separator: char) -> Vec<Requisite<RT>> {
-        let kv = data.split(separator);
+    /// Установка диалекта, отклонения которого допускаются при разборе.
+    pub fn with_dialect(mut self, dialect: Dialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
 
-        kv.into_iter()
+    /// Ключи приводятся к ключам стандарта по правилам версии из заголовка.
+    fn read_requisites(&self, data: &str, header: &PaymentHeader) -> Vec<Requisite<RT>> {
+        let version = self.versions.get(header.version).unwrap_or(&Version::V0001);
+
+        data.split(header.separator as char)
             .flat_map(|kv| kv.split_once('='))
-            .flat_map(|kv| kv.try_into())
+            .flat_map(|(key, value)| (self.dialect.canonical_key(key, version), value).try_into())
             .collect()
     }
 }
 
 impl<RT: CustomRequisites> ParserStrategy<RT> for PaymentParser<LooseParser, RT> {
     fn parse_from_str(&self, val: &str) -> crate::Result<Payment<RT>> {
-        let header = self.read_payment_header(val, false)?;
-
-        let data = val[8..].to_string();
+        let (header, data) = self.read_payment_header(val, false)?;
 
-        let requisites = self.read_requisites(&data, header.separator as char);
+        let mut requisites = self.read_requisites(data, &header);
+        self.retain_version_requisites(&header, &mut requisites);
 
-        Ok(Payment { header, requisites })
+        Ok(Payment {
+            header,
+            requisites,
+            layout: Vec::new(),
+        })
     }
 
     fn parse_from_bytes(&self, bytes: &[u8]) -> crate::Result<Payment<RT>> {
-        let header = self.read_payment_header_bytes(bytes)?;
+        let (header, body) = self.read_payment_header_bytes(bytes)?;
 
         let data = self.decode_payment_body(
             header.encoding,
-            &bytes[8..],
+            body,
             encoding::DecoderTrap::Replace,
             |val| Ok(String::from_utf8_lossy(val).to_string()),
         )?;
 
-        let requisites = self.read_requisites(&data, header.separator as char);
+        let mut requisites = self.read_requisites(&data, &header);
+        self.retain_version_requisites(&header, &mut requisites);
 
-        Ok(Payment { header, requisites })
+        Ok(Payment {
+            header,
+            requisites,
+            layout: Vec::new(),
+        })
     }
 }
 
 impl<RT: CustomRequisites> PaymentParser<LooseParser, RT> {
-    fn read_requisites(&self, data: &str, separator: char) -> Vec<Requisite<RT>> {
-        let kv = data.split(separator);
+    /// Установка диалекта, отклонения которого допускаются при разборе.
+    pub fn with_dialect(mut self, dialect: Dialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
 
-        kv.into_iter()
+    /// Ключи приводятся к ключам стандарта по правилам версии из заголовка.
+    fn read_requisites(&self, data: &str, header: &PaymentHeader) -> Vec<Requisite<RT>> {
+        let version = self.versions.get(header.version).unwrap_or(&Version::V0001);
+
+        data.split(header.separator as char)
             .flat_map(|kv| kv.split_once('='))
-            .flat_map(|kv| kv.try_into())
+            .flat_map(|(key, value)| (self.dialect.canonical_key(key, version), value).try_into())
             .collect()
     }
 }
 
-impl<T: ParserStrategyType, RT: CustomRequisites> PaymentParser<T, RT> {
-    fn read_payment_header_bytes(&self, bytes: &[u8]) -> super::Result<PaymentHeader> {
-        if bytes.len() < 8 {
-            return Err(super::Error::CorruptedHeader(
-                "Не возможно сформировать заголовок, так как длина меньше 8".into(),
-            ));
+impl<RT: CustomRequisites> ParserStrategy<RT> for PaymentParser<RoundTripParser, RT> {
+    fn parse_from_str(&self, val: &str) -> crate::Result<Payment<RT>> {
+        let (header, data) = self.read_payment_header(val, true)?;
+
+        Ok(self.read_segments(header, data))
+    }
+
+    fn parse_from_bytes(&self, bytes: &[u8]) -> crate::Result<Payment<RT>> {
+        let (header, body) = self.read_payment_header_bytes(bytes)?;
+
+        let data = self.decode_payment_body(
+            header.encoding,
+            body,
+            encoding::DecoderTrap::Strict,
+            |val| String::from_utf8(val.to_vec()).map_err(|_| super::Error::DecodingError),
+        )?;
+
+        Ok(self.read_segments(header, &data))
+    }
+}
+
+impl<RT: CustomRequisites> PaymentParser<RoundTripParser, RT> {
+    /// Сегмент сохраняется как реквизит, только если реквизит соответствует правилам версии
+    /// и кодируется обратно в тот же сегмент.
+    fn read_segments(&self, header: PaymentHeader, data: &str) -> Payment<RT> {
+        let mut requisites = Vec::new();
+        let mut layout = Vec::new();
+
+        let version = self.versions.get(header.version);
+
+        for segment in data.split(header.separator as char) {
+            let requisite = segment
+                .split_once('=')
+                .and_then(|kv| Requisite::<RT>::try_from(kv).ok())
+                .filter(|requisite| requisite.to_string() == segment)
+                .filter(|requisite| {
+                    version.is_none_or(|version| {
+                        version
+                            .validate_with(requisite, self.length_unit, header.encoding)
+                            .is_ok()
+                    })
+                });
+
+            match requisite {
+                Some(requisite) => {
+                    requisites.push(requisite);
+                    layout.push(RawSegment::Requisite);
+                }
+                None => layout.push(RawSegment::Unparsed(segment.into())),
+            }
         }
 
-        let format_id = &bytes[0..2];
+        // Реквизиты сверх разметки записываются в конце, поэтому места в конце разметки не нужны
+        while layout.last() == Some(&RawSegment::Requisite) {
+            layout.pop();
+        }
 
-        if format_id != FORMAT_ID_BYTES {
-            return Err(super::Error::WrongFormatId([format_id[0], format_id[1]]));
+        Payment {
+            header,
+            requisites,
+            layout,
         }
+    }
+}
 
-        let version = &bytes[2..6];
-        if version != self.version_id {
+impl<T: ParserStrategyType, RT: CustomRequisites> PaymentParser<T, RT> {
+    /// Возвращает заголовок и тело платежа.
+    fn read_payment_header_bytes<'a>(
+        &self,
+        bytes: &'a [u8],
+    ) -> super::Result<(PaymentHeader, &'a [u8])> {
+        let (header, len) = match self.dialect.read_header(bytes) {
+            Some(header) => header,
+            None => {
+                if bytes.len() < 8 {
+                    return Err(super::Error::CorruptedHeader(
+                        "Не возможно сформировать заголовок, так как длина меньше 8".into(),
+                    ));
+                }
+
+                (PaymentHeader::try_from(&bytes[..8])?, 8)
+            }
+        };
+
+        if self.versions.get(header.version).is_none() {
             return Err(super::Error::UnsupportedVersion {
-                passed: [version[0], version[1], version[2], version[3]],
-                current: self.version_id,
+                passed: header.version,
+                supported: self.versions.ids().collect(),
             });
         }
 
-        let encoding: PaymentEncoding = bytes[6].try_into()?;
-        let separator = bytes[7];
-
-        Ok(PaymentHeader {
-            format_id: FORMAT_ID_BYTES,
-            version: self.version_id,
-            encoding,
-            separator,
-        })
+        Ok((header, &bytes[len..]))
     }
 
-    fn read_payment_header(&self, val: &str, check_encoding: bool) -> super::Result<PaymentHeader> {
-        let bytes = val.chars().take(8).map(|c| c as u8).collect::<Vec<_>>();
-        let header = self.read_payment_header_bytes(&bytes)?;
+    /// Возвращает заголовок и тело платежа.
+    fn read_payment_header<'a>(
+        &self,
+        val: &'a str,
+        check_encoding: bool,
+    ) -> super::Result<(PaymentHeader, &'a str)> {
+        let (header, body) = self.read_payment_header_bytes(val.as_bytes())?;
 
         if check_encoding && header.encoding != PaymentEncoding::Utf8 {
             return Err(super::Error::CorruptedHeader(
@@ -211,7 +361,8 @@ impl<T: ParserStrategyType, RT: CustomRequisites> PaymentParser<T, RT> {
             ));
         }
 
-        Ok(header)
+        // Заголовок состоит только из ASCII символов, поэтому тело начинается на границе символа
+        Ok((header, &val[val.len() - body.len()..]))
     }
 
     fn decode_payment_body(
@@ -279,12 +430,45 @@ impl<T: ParserStrategyType, RT: CustomRequisites> PaymentParser<T, RT> {
 
         Ok(())
     }
+
+    fn validate_version_requisites(
+        &self,
+        header: &PaymentHeader,
+        requisites: &[Requisite<RT>],
+    ) -> super::Result<()> {
+        let Some(version) = self.versions.get(header.version) else {
+            return Err(super::Error::UnsupportedVersion {
+                passed: header.version,
+                supported: self.versions.ids().collect(),
+            });
+        };
+
+        requisites.iter().try_for_each(|requisite| {
+            version.validate_with(requisite, self.length_unit, header.encoding)
+        })
+    }
+
+    fn retain_version_requisites(
+        &self,
+        header: &PaymentHeader,
+        requisites: &mut Vec<Requisite<RT>>,
+    ) {
+        if let Some(version) = self.versions.get(header.version) {
+            requisites.retain(|requisite| {
+                version
+                    .validate_with(requisite, self.length_unit, header.encoding)
+                    .is_ok()
+            });
+        }
+    }
 }
 
 impl<T: ParserStrategyType, RT: CustomRequisites> Default for PaymentParser<T, RT> {
     fn default() -> Self {
         Self {
-            version_id: VERSION_0001_BYTES,
+            versions: VersionRegistry::default(),
+            dialect: Dialect::default(),
+            length_unit: LengthUnit::default(),
             _req_marker: PhantomData,
             _marker: PhantomData,
         }
@@ -304,3 +488,10 @@ impl ParserStrategyType for RequisiteToleranceParser {}
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct LooseParser;
 impl ParserStrategyType for LooseParser {}
+
+/// Парсер точного воспроизведения.
+///
+/// Тело платежа декодируется строго, поэтому платеж с байтами, недопустимыми в его кодировке, не разбирается.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RoundTripParser;
+impl ParserStrategyType for RoundTripParser {}
@@ -0,0 +1,864 @@
+//! Типизированные реквизиты плательщика.
+
+use core::{fmt::Display, str::FromStr};
+
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+
+use crate::{CustomRequisites, Payment, Requisite};
+
+/// Окончания отчеств: Иванович, Ильич, Ивановна, Ильинична.
+const PATRONYMIC_SUFFIXES: [&str; 3] = ["ич", "вна", "чна"];
+
+/// Отдельные слова, завершающие тюркские отчества: «Гасан оглы».
+const PATRONYMIC_WORDS: [&str; 4] = ["оглы", "кызы", "улы", "уулу"];
+
+/// ФИО плательщика.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PayerName {
+    last_name: Box<str>,
+    first_name: Box<str>,
+    middle_name: Option<Box<str>>,
+}
+
+impl PayerName {
+    /// Создание ФИО из отдельных частей.
+    pub fn new(
+        last_name: &str,
+        first_name: &str,
+        middle_name: Option<&str>,
+    ) -> super::Result<Self> {
+        let part = |key: &str, value: &str| {
+            let value = normalize(value);
+            if value.is_empty() {
+                return Err(super::Error::WrongPair(key.into(), value.into()));
+            }
+            Ok(value.into_boxed_str())
+        };
+
+        Ok(Self {
+            last_name: part("LastName", last_name)?,
+            first_name: part("FirstName", first_name)?,
+            middle_name: middle_name
+                .map(|middle_name| part("MiddleName", middle_name))
+                .transpose()?,
+        })
+    }
+
+    /// Фамилия.
+    pub fn last_name(&self) -> &str {
+        &self.last_name
+    }
+
+    /// Имя.
+    pub fn first_name(&self) -> &str {
+        &self.first_name
+    }
+
+    /// Отчество.
+    pub fn middle_name(&self) -> Option<&str> {
+        self.middle_name.as_deref()
+    }
+
+    /// Реквизиты `LastName`, `FirstName` и, если есть отчество, `MiddleName`.
+    pub fn requisites<T: CustomRequisites>(&self) -> Vec<Requisite<T>> {
+        let mut requisites = alloc::vec![
+            Requisite::LastName(self.last_name.clone()),
+            Requisite::FirstName(self.first_name.clone()),
+        ];
+        requisites.extend(self.middle_name.clone().map(Requisite::MiddleName));
+        requisites
+    }
+}
+
+impl FromStr for PayerName {
+    type Err = super::Error;
+
+    /// Разбор ФИО из строки «Фамилия Имя Отчество».
+    ///
+    /// Отчество определяется по окончанию (`-ович`, `-овна`, `оглы` и т.п.).
+    /// Слова перед именем относятся к фамилии, поэтому двойная фамилия может быть записана
+    /// как через дефис, так и через пробел: «Римский Корсаков Николай Андреевич».
+    /// Если последнее слово не похоже на отчество, ФИО считается записанным без отчества.
+    fn from_str(s: &str) -> super::Result<Self> {
+        let words = s.split_whitespace().collect::<Vec<_>>();
+
+        let patronymic_len = match words.as_slice() {
+            [.., word] if PATRONYMIC_WORDS.contains(&word.to_lowercase().as_str()) => 2,
+            [.., word] if is_patronymic(word) => 1,
+            _ => 0,
+        };
+
+        // Для фамилии и имени нужно хотя бы по одному слову
+        let patronymic_len = if words.len() < patronymic_len + 2 {
+            0
+        } else {
+            patronymic_len
+        };
+
+        if words.len() < 2 {
+            return Err(super::Error::WrongPair("LastName".into(), s.into()));
+        }
+
+        let (names, middle_name) = words.split_at(words.len() - patronymic_len);
+        let (last_name, first_name) = names.split_at(names.len() - 1);
+
+        Self::new(
+            &last_name.join(" "),
+            first_name[0],
+            (!middle_name.is_empty())
+                .then(|| middle_name.join(" "))
+                .as_deref(),
+        )
+    }
+}
+
+impl Display for PayerName {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} {}", self.last_name, self.first_name)?;
+
+        if let Some(middle_name) = &self.middle_name {
+            write!(f, " {}", middle_name)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Номер телефона в формате E.164.
+///
+/// Российские номера приводятся к виду `+7XXXXXXXXXX`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Phone(Box<str>);
+
+impl Phone {
+    /// Номер в формате E.164.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Номер без кода страны, если номер российский.
+    pub fn national(&self) -> Option<&str> {
+        self.0.strip_prefix("+7")
+    }
+
+    /// Реквизит `Phone` с номером в формате E.164.
+    pub fn requisite<T: CustomRequisites>(&self) -> Requisite<T> {
+        Requisite::Phone(self.0.clone())
+    }
+}
+
+impl FromStr for Phone {
+    type Err = super::Error;
+
+    /// Разбор номера, записанного с пробелами, скобками, точками и дефисами.
+    ///
+    /// Российский номер принимается в виде `+7XXXXXXXXXX`, `8XXXXXXXXXX`, `7XXXXXXXXXX` или `XXXXXXXXXX`,
+    /// номер другой страны - только с `+` и кодом страны.
+    /// Номер без кода страны должен начинаться с кода зоны 3, 4, 8 или 9.
+    fn from_str(s: &str) -> super::Result<Self> {
+        let wrong_phone = || super::Error::WrongPair("Phone".into(), s.into());
+
+        let (plus, rest) = match s.trim().strip_prefix('+') {
+            Some(rest) => (true, rest),
+            None => (false, s.trim()),
+        };
+
+        let mut digits = String::with_capacity(rest.len());
+        for c in rest.chars() {
+            match c {
+                '0'..='9' => digits.push(c),
+                ' ' | '(' | ')' | '-' | '.' => {}
+                _ => return Err(wrong_phone()),
+            }
+        }
+
+        let national = match (plus, digits.len(), digits.as_bytes().first()) {
+            (true, 11, Some(b'7')) | (false, 11, Some(b'7' | b'8')) => &digits[1..],
+            (false, 10, Some(b'3' | b'4' | b'8' | b'9')) => &digits,
+            (true, 8..=15, Some(b'1'..=b'9')) if !digits.starts_with('7') => {
+                return Ok(Self(format!("+{}", digits).into()))
+            }
+            _ => return Err(wrong_phone()),
+        };
+
+        Ok(Self(format!("+7{}", national).into()))
+    }
+}
+
+impl Display for Phone {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// СНИЛС в формате `XXX-XXX-XXX YY`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Snils(Box<str>);
+
+impl Snils {
+    /// Номера до `001-001-998` включительно выдавались без контрольной суммы.
+    const UNCHECKED_MAX: u32 = 1_001_998;
+
+    /// СНИЛС в формате `XXX-XXX-XXX YY`.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// СНИЛС из 11 цифр без разделителей.
+    pub fn digits(&self) -> String {
+        self.0.chars().filter(char::is_ascii_digit).collect()
+    }
+
+    /// Реквизит `PensAcc` со СНИЛС в формате `XXX-XXX-XXX YY`.
+    pub fn requisite<T: CustomRequisites>(&self) -> Requisite<T> {
+        Requisite::PensAcc(self.0.clone())
+    }
+
+    /// Контрольное число для первых 9 цифр номера.
+    pub(crate) fn checksum(number: &[u8]) -> u32 {
+        let sum = number
+            .iter()
+            .zip((1..=9).rev())
+            .map(|(digit, weight)| (digit - b'0') as u32 * weight)
+            .sum::<u32>();
+
+        match sum % 101 {
+            100 => 0,
+            sum => sum,
+        }
+    }
+}
+
+impl FromStr for Snils {
+    type Err = super::Error;
+
+    /// Разбор СНИЛС из 11 цифр, записанных слитно или с пробелами и дефисами: `XXX-XXX-XXX YY`.
+    fn from_str(s: &str) -> super::Result<Self> {
+        let wrong_snils = || super::Error::WrongPair("PensAcc".into(), s.into());
+
+        let digits = s
+            .bytes()
+            .filter(|b| !matches!(b, b' ' | b'-'))
+            .collect::<Vec<_>>();
+
+        if digits.len() != 11 || !digits.iter().all(u8::is_ascii_digit) {
+            return Err(wrong_snils());
+        }
+
+        let (number, control) = digits.split_at(9);
+        let number_value = number
+            .iter()
+            .fold(0, |acc, digit| acc * 10 + (digit - b'0') as u32);
+        let control = control
+            .iter()
+            .fold(0, |acc, digit| acc * 10 + (digit - b'0') as u32);
+
+        if number_value > Self::UNCHECKED_MAX && Self::checksum(number) != control {
+            return Err(wrong_snils());
+        }
+
+        let number = core::str::from_utf8(number).map_err(|_| wrong_snils())?;
+        Ok(Self(
+            format!(
+                "{}-{}-{} {:02}",
+                &number[..3],
+                &number[3..6],
+                &number[6..],
+                control
+            )
+            .into(),
+        ))
+    }
+}
+
+impl Display for Snils {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Вид документа, удостоверяющего личность плательщика.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PayerIdType {
+    /// Паспорт гражданина Российской Федерации
+    Passport,
+
+    /// Свидетельство о рождении, выданное органами ЗАГС
+    BirthCertificate,
+
+    /// Паспорт моряка
+    SeamanPassport,
+
+    /// Удостоверение личности военнослужащего
+    MilitaryId,
+
+    /// Военный билет
+    MilitaryCard,
+
+    /// Временное удостоверение личности гражданина Российской Федерации
+    TemporaryId,
+
+    /// Свидетельство о рождении, выданное органами иностранного государства
+    ForeignBirthCertificate,
+
+    /// Паспорт иностранного гражданина
+    ForeignPassport,
+
+    /// Вид на жительство
+    ResidencePermit,
+
+    /// Разрешение на временное проживание
+    TemporaryResidencePermit,
+
+    /// Удостоверение беженца
+    RefugeeId,
+
+    /// Миграционная карта
+    MigrationCard,
+
+    /// Паспорт гражданина СССР
+    UssrPassport,
+
+    /// СНИЛС
+    Snils,
+
+    /// Водительское удостоверение
+    DriverLicense,
+
+    /// Свидетельство о регистрации транспортного средства
+    VehicleRegistration,
+}
+
+impl PayerIdType {
+    /// Все виды документов в порядке кодов.
+    pub const ALL: [PayerIdType; 16] = [
+        PayerIdType::Passport,
+        PayerIdType::BirthCertificate,
+        PayerIdType::SeamanPassport,
+        PayerIdType::MilitaryId,
+        PayerIdType::MilitaryCard,
+        PayerIdType::TemporaryId,
+        PayerIdType::ForeignBirthCertificate,
+        PayerIdType::ForeignPassport,
+        PayerIdType::ResidencePermit,
+        PayerIdType::TemporaryResidencePermit,
+        PayerIdType::RefugeeId,
+        PayerIdType::MigrationCard,
+        PayerIdType::UssrPassport,
+        PayerIdType::Snils,
+        PayerIdType::DriverLicense,
+        PayerIdType::VehicleRegistration,
+    ];
+
+    /// Название вида документа.
+    pub fn description(&self) -> &'static str {
+        match self {
+            PayerIdType::Passport => "Паспорт гражданина Российской Федерации",
+            PayerIdType::BirthCertificate => "Свидетельство о рождении, выданное органами ЗАГС",
+            PayerIdType::SeamanPassport => "Паспорт моряка",
+            PayerIdType::MilitaryId => "Удостоверение личности военнослужащего",
+            PayerIdType::MilitaryCard => "Военный билет",
+            PayerIdType::TemporaryId => {
+                "Временное удостоверение личности гражданина Российской Федерации"
+            }
+            PayerIdType::ForeignBirthCertificate => {
+                "Свидетельство о рождении, выданное органами иностранного государства"
+            }
+            PayerIdType::ForeignPassport => "Паспорт иностранного гражданина",
+            PayerIdType::ResidencePermit => "Вид на жительство",
+            PayerIdType::TemporaryResidencePermit => "Разрешение на временное проживание",
+            PayerIdType::RefugeeId => "Удостоверение беженца",
+            PayerIdType::MigrationCard => "Миграционная карта",
+            PayerIdType::UssrPassport => "Паспорт гражданина СССР",
+            PayerIdType::Snils => "СНИЛС",
+            PayerIdType::DriverLicense => "Водительское удостоверение",
+            PayerIdType::VehicleRegistration => {
+                "Свидетельство о регистрации транспортного средства"
+            }
+        }
+    }
+
+    /// Код вида документа.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PayerIdType::Passport => "01",
+            PayerIdType::BirthCertificate => "02",
+            PayerIdType::SeamanPassport => "03",
+            PayerIdType::MilitaryId => "04",
+            PayerIdType::MilitaryCard => "05",
+            PayerIdType::TemporaryId => "06",
+            PayerIdType::ForeignBirthCertificate => "07",
+            PayerIdType::ForeignPassport => "08",
+            PayerIdType::ResidencePermit => "09",
+            PayerIdType::TemporaryResidencePermit => "10",
+            PayerIdType::RefugeeId => "11",
+            PayerIdType::MigrationCard => "12",
+            PayerIdType::UssrPassport => "13",
+            PayerIdType::Snils => "14",
+            PayerIdType::DriverLicense => "22",
+            PayerIdType::VehicleRegistration => "24",
+        }
+    }
+
+    /// Проверка формата номера документа.
+    ///
+    /// Пробелы и дефисы между серией и номером не учитываются.
+    /// Для документов без единого формата номер должен быть непустым.
+    pub fn check_number(&self, number: &str) -> super::Result<()> {
+        let chars = number
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '-'))
+            .collect::<Vec<_>>();
+
+        let valid = match self {
+            // Серия из 4 цифр и номер из 6 цифр
+            PayerIdType::Passport => is_digits(&chars, 10),
+            // Римская серия, две буквы и номер из 6 цифр: `IV-МЮ 123456`
+            PayerIdType::BirthCertificate | PayerIdType::UssrPassport => {
+                let roman = chars
+                    .iter()
+                    .take_while(|c| matches!(c, 'I' | 'V' | 'X' | 'L' | 'C'))
+                    .count();
+                let rest = &chars[roman..];
+
+                roman > 0
+                    && rest.len() == 8
+                    && rest[..2].iter().all(is_cyrillic_upper)
+                    && is_digits(&rest[2..], 6)
+            }
+            // Серия из двух букв и номер из 7 цифр
+            PayerIdType::SeamanPassport | PayerIdType::MilitaryId | PayerIdType::MilitaryCard => {
+                chars.len() == 9
+                    && chars[..2].iter().all(is_cyrillic_upper)
+                    && is_digits(&chars[2..], 7)
+            }
+            // Серия из 2 цифр и номер из 7 цифр
+            PayerIdType::ResidencePermit => is_digits(&chars, 9),
+            PayerIdType::Snils => number.parse::<Snils>().is_ok(),
+            // Серия из 2 цифр и 2 цифр или букв, номер из 6 цифр
+            PayerIdType::DriverLicense | PayerIdType::VehicleRegistration => {
+                chars.len() == 10
+                    && is_digits(&chars[..2], 2)
+                    && chars[2..4]
+                        .iter()
+                        .all(|c| c.is_ascii_digit() || is_cyrillic_upper(c))
+                    && is_digits(&chars[4..], 6)
+            }
+            PayerIdType::TemporaryId
+            | PayerIdType::ForeignBirthCertificate
+            | PayerIdType::ForeignPassport
+            | PayerIdType::TemporaryResidencePermit
+            | PayerIdType::RefugeeId
+            | PayerIdType::MigrationCard => !chars.is_empty(),
+        };
+
+        if !valid {
+            return Err(super::Error::WrongPair("PayerIdNum".into(), number.into()));
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for PayerIdType {
+    type Err = super::Error;
+
+    fn from_str(s: &str) -> super::Result<Self> {
+        PayerIdType::ALL
+            .into_iter()
+            .find(|id_type| id_type.as_str() == s)
+            .ok_or_else(|| super::Error::WrongPair("PayerIdType".into(), s.into()))
+    }
+}
+
+impl Display for PayerIdType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Документ, удостоверяющий личность плательщика.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PayerId {
+    id_type: PayerIdType,
+    number: Box<str>,
+}
+
+impl PayerId {
+    /// Создание документа с проверкой формата номера.
+    pub fn new(id_type: PayerIdType, number: &str) -> super::Result<Self> {
+        let number = number.trim();
+        id_type.check_number(number)?;
+
+        Ok(Self {
+            id_type,
+            number: number.into(),
+        })
+    }
+
+    /// Вид документа.
+    pub fn id_type(&self) -> PayerIdType {
+        self.id_type
+    }
+
+    /// Номер документа.
+    pub fn number(&self) -> &str {
+        &self.number
+    }
+
+    /// Реквизиты `PayerIdType` и `PayerIdNum`.
+    pub fn requisites<T: CustomRequisites>(&self) -> Vec<Requisite<T>> {
+        alloc::vec![
+            Requisite::PayerIdType(self.id_type.as_str().into()),
+            Requisite::PayerIdNum(self.number.clone()),
+        ]
+    }
+}
+
+impl<T: CustomRequisites> Payment<T> {
+    /// ФИО плательщика из реквизитов `LastName`, `FirstName` и `MiddleName`.
+    ///
+    /// Возвращает `None`, если фамилия или имя не указаны или пусты.
+    pub fn payer_name(&self) -> Option<PayerName> {
+        PayerName::new(
+            self.get("LastName")?,
+            self.get("FirstName")?,
+            self.get("MiddleName")
+                .filter(|name| !name.trim().is_empty()),
+        )
+        .ok()
+    }
+
+    /// Номер телефона.
+    ///
+    /// Значение реквизита хранится как есть, поэтому ошибка возвращается, если номер некорректен.
+    pub fn phone(&self) -> super::Result<Option<Phone>> {
+        self.get("Phone").map(str::parse).transpose()
+    }
+
+    /// СНИЛС плательщика.
+    ///
+    /// Значение реквизита хранится как есть, поэтому ошибка возвращается, если СНИЛС некорректен.
+    pub fn snils(&self) -> super::Result<Option<Snils>> {
+        self.get("PensAcc").map(str::parse).transpose()
+    }
+
+    /// Вид документа, удостоверяющего личность плательщика.
+    ///
+    /// Значение реквизита хранится как есть, поэтому ошибка возвращается, если код вида документа неизвестен.
+    pub fn payer_id_type(&self) -> super::Result<Option<PayerIdType>> {
+        self.get("PayerIdType").map(str::parse).transpose()
+    }
+
+    /// Документ, удостоверяющий личность плательщика.
+    ///
+    /// Возвращает `None`, если не указаны ни вид, ни номер документа, и ошибку,
+    /// если указан только один из реквизитов или номер не соответствует виду документа.
+    pub fn payer_id(&self) -> super::Result<Option<PayerId>> {
+        match (self.payer_id_type()?, self.get("PayerIdNum")) {
+            (Some(id_type), Some(number)) => PayerId::new(id_type, number).map(Some),
+            (Some(_), None) => Err(super::Error::MissingRequisite("PayerIdNum".into())),
+            (None, Some(_)) => Err(super::Error::MissingRequisite("PayerIdType".into())),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
+fn is_patronymic(word: &str) -> bool {
+    let word = word.to_lowercase();
+    let chars = word.chars().count();
+
+    PATRONYMIC_SUFFIXES
+        .iter()
+        .any(|suffix| word.ends_with(suffix) && chars > suffix.chars().count() + 1)
+}
+
+fn is_digits(chars: &[char], len: usize) -> bool {
+    chars.len() == len && chars.iter().all(char::is_ascii_digit)
+}
+
+fn is_cyrillic_upper(c: &char) -> bool {
+    matches!(c, 'А'..='Я' | 'Ё')
+}
+
+fn normalize(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fixtures::required, Error, NoCustomRequisites, ParserStrategy, PayerId, PayerIdType,
+        PayerName, Payment, Phone, RawRequisite, Requisite, Snils,
+    };
+
+    #[test]
+    fn payer_name_parse_test() {
+        for (raw, last_name, first_name, middle_name) in [
+            ("Иванов Иван Иванович", "Иванов", "Иван", Some("Иванович")),
+            (
+                "  Петров-Водкин   Кузьма Сергеевич ",
+                "Петров-Водкин",
+                "Кузьма",
+                Some("Сергеевич"),
+            ),
+            (
+                "Римский Корсаков Николай Андреевич",
+                "Римский Корсаков",
+                "Николай",
+                Some("Андреевич"),
+            ),
+            (
+                "Алиев Рашид Гасан оглы",
+                "Алиев",
+                "Рашид",
+                Some("Гасан оглы"),
+            ),
+            ("Смит Джон", "Смит", "Джон", None),
+            ("Сальвадор Дали Мария", "Сальвадор Дали", "Мария", None),
+            ("Петрова Ильинична", "Петрова", "Ильинична", None),
+        ] {
+            assert_eq!(
+                raw.parse::<PayerName>(),
+                PayerName::new(last_name, first_name, middle_name)
+            );
+        }
+
+        assert_eq!(
+            "Иванов".parse::<PayerName>(),
+            Err(Error::WrongPair("LastName".into(), "Иванов".into()))
+        );
+    }
+
+    #[test]
+    fn phone_test() {
+        for raw in [
+            "8 (912) 345-67-89",
+            "+79123456789",
+            "+7 912 345 67 89",
+            "79123456789",
+            "9123456789",
+            "912.345.67.89",
+        ] {
+            let phone = raw.parse::<Phone>().unwrap();
+            assert_eq!(phone.as_str(), "+79123456789");
+            assert_eq!(phone.national(), Some("9123456789"));
+        }
+
+        let phone = "+375 29 123-45-67".parse::<Phone>().unwrap();
+        assert_eq!(phone.as_str(), "+375291234567");
+        assert_eq!(phone.national(), None);
+
+        for wrong in [
+            "912345678",
+            "+0 912 345 67 89",
+            "0123456789",
+            "7123456789",
+            "+7912345678",
+            "тел. 9123456789",
+            "+",
+        ] {
+            assert_eq!(
+                wrong.parse::<Phone>(),
+                Err(Error::WrongPair("Phone".into(), wrong.into()))
+            );
+        }
+    }
+
+    #[test]
+    fn snils_test() {
+        for raw in [
+            "112-233-445 95",
+            "11223344595",
+            "112 233 445 95",
+            "112-233-445-95",
+        ] {
+            let snils = raw.parse::<Snils>().unwrap();
+            assert_eq!(snils.as_str(), "112-233-445 95");
+            assert_eq!(snils.digits(), "11223344595");
+        }
+
+        // Контрольная сумма 100 и 101 записывается как 00, больше 101 - как остаток от деления на 101
+        assert!("001-019-989 00".parse::<Snils>().is_ok());
+        assert!("001-019-998 00".parse::<Snils>().is_ok());
+        assert!("999-999-999 01".parse::<Snils>().is_ok());
+
+        // Ранние номера выдавались без контрольной суммы
+        assert!("001-001-998 42".parse::<Snils>().is_ok());
+
+        for wrong in ["112-233-445 96", "1122334459", "112-233-445 9Б", ""] {
+            assert_eq!(
+                wrong.parse::<Snils>(),
+                Err(Error::WrongPair("PensAcc".into(), wrong.into()))
+            );
+        }
+    }
+
+    #[test]
+    fn snils_payment_test() {
+        let raw = "ST00012|Name=ООО «Три кита»|PersonalAcc=40702810138250123017|BankName=ОАО \"БАНК\"|BIC=044525225|CorrespAcc=30101810400000000225|PensAcc=11223344595";
+
+        let payment = Payment::parser().parse_from_str(raw).unwrap();
+        assert_eq!(
+            payment.snils().unwrap().as_ref().map(Snils::as_str),
+            Some("112-233-445 95")
+        );
+        assert_eq!(payment.to_utf8_lossy().as_deref(), Ok(raw));
+
+        let raw = raw.replace("11223344595", "11223344596");
+        assert_eq!(
+            Payment::parser().parse_from_str(&raw),
+            Err(Error::WrongPair("PensAcc".into(), "11223344596".into()))
+        );
+
+        let payment = Payment::requisite_tolerance_parser()
+            .parse_from_str(&raw)
+            .unwrap();
+        assert_eq!(payment.get("PensAcc"), Some("11223344596"));
+        assert_eq!(
+            payment.snils(),
+            Err(Error::WrongPair("PensAcc".into(), "11223344596".into()))
+        );
+    }
+
+    #[test]
+    fn payer_id_test() {
+        for (id_type, number) in [
+            (PayerIdType::Passport, "4508 123456"),
+            (PayerIdType::BirthCertificate, "IV-МЮ 123456"),
+            (PayerIdType::UssrPassport, "XIV-АБ 654321"),
+            (PayerIdType::MilitaryCard, "АБ 1234567"),
+            (PayerIdType::ResidencePermit, "82 1234567"),
+            (PayerIdType::Snils, "112-233-445 95"),
+            (PayerIdType::DriverLicense, "77 ОА 123456"),
+            (PayerIdType::VehicleRegistration, "7712 123456"),
+            (PayerIdType::ForeignPassport, "AB1234567"),
+        ] {
+            let id = PayerId::new(id_type, number).unwrap();
+            assert_eq!((id.id_type(), id.number()), (id_type, number));
+        }
+
+        for (id_type, number) in [
+            (PayerIdType::Passport, "4508 12345"),
+            (PayerIdType::BirthCertificate, "МЮ 123456"),
+            (PayerIdType::MilitaryId, "AB 1234567"),
+            (PayerIdType::Snils, "112-233-445 96"),
+            (PayerIdType::DriverLicense, "7 ОА 1234567"),
+            (PayerIdType::MigrationCard, " "),
+        ] {
+            assert_eq!(
+                PayerId::new(id_type, number),
+                Err(Error::WrongPair("PayerIdNum".into(), number.trim().into()))
+            );
+        }
+
+        assert_eq!(
+            "09".parse::<PayerIdType>(),
+            Ok(PayerIdType::ResidencePermit)
+        );
+        assert_eq!(
+            "99".parse::<PayerIdType>(),
+            Err(Error::WrongPair("PayerIdType".into(), "99".into()))
+        );
+    }
+
+    #[test]
+    fn payer_id_payment_test() {
+        let raw = "ST00012|Name=ООО «Три кита»|PersonalAcc=40702810138250123017|BankName=ОАО \"БАНК\"|BIC=044525225|CorrespAcc=30101810400000000225|PayerIdType=01|PayerIdNum=4508 123456";
+
+        let payment = Payment::parser().parse_from_str(raw).unwrap();
+        assert_eq!(payment.payer_id_type(), Ok(Some(PayerIdType::Passport)));
+        assert_eq!(
+            payment.payer_id(),
+            Ok(Some(
+                PayerId::new(PayerIdType::Passport, "4508 123456").unwrap()
+            ))
+        );
+
+        let payment = Payment::parser()
+            .parse_from_str(&raw.replace("=01|", "=05|"))
+            .unwrap();
+        assert_eq!(
+            payment.payer_id(),
+            Err(Error::WrongPair("PayerIdNum".into(), "4508 123456".into()))
+        );
+
+        let payment = Payment::parser()
+            .parse_from_str(raw.trim_end_matches("|PayerIdNum=4508 123456"))
+            .unwrap();
+        assert_eq!(
+            payment.payer_id(),
+            Err(Error::MissingRequisite("PayerIdNum".into()))
+        );
+
+        let raw = raw.replace("=01|", "=1|");
+        assert_eq!(
+            Payment::parser().parse_from_str(&raw),
+            Err(Error::WrongPair("PayerIdType".into(), "1".into()))
+        );
+
+        let payment = Payment::requisite_tolerance_parser()
+            .parse_from_str(&raw)
+            .unwrap();
+        assert_eq!(payment.get("PayerIdType"), Some("1"));
+        assert_eq!(
+            payment.payer_id(),
+            Err(Error::WrongPair("PayerIdType".into(), "1".into()))
+        );
+    }
+
+    #[test]
+    fn phone_parser_test() {
+        let raw = "ST00012|Name=ООО «Три кита»|PersonalAcc=40702810138250123017|BankName=ОАО \"БАНК\"|BIC=044525225|CorrespAcc=30101810400000000225|Phone=8 (912) 345-67-89";
+
+        let payment = Payment::parser().parse_from_str(raw).unwrap();
+        assert_eq!(payment.phone(), "+79123456789".parse().map(Some));
+        assert_eq!(payment.to_utf8_lossy().as_deref(), Ok(raw));
+
+        let raw = raw.replace("8 (912) 345-67-89", "неизвестен");
+        assert_eq!(
+            Payment::parser().parse_from_str(&raw),
+            Err(Error::WrongPair("Phone".into(), "неизвестен".into()))
+        );
+
+        let wrong_phone = Err(Error::WrongPair("Phone".into(), "неизвестен".into()));
+        let payment = Payment::requisite_tolerance_parser()
+            .parse_from_str(&raw)
+            .unwrap();
+        assert_eq!(payment.get("Phone"), Some("неизвестен"));
+        assert_eq!(payment.phone(), wrong_phone);
+
+        let payment = Payment::loose_parser().parse_from_str(&raw).unwrap();
+        assert_eq!(payment.get("Phone"), Some("неизвестен"));
+
+        assert_eq!(
+            Payment::<RawRequisite>::round_trip_custom_parser()
+                .parse_from_str(&raw)
+                .unwrap()
+                .to_utf8_lossy(),
+            Ok(raw)
+        );
+    }
+
+    #[test]
+    fn payer_name_payment_test() {
+        let name: PayerName = "Иванов Иван Иванович".parse().unwrap();
+        assert_eq!(alloc::format!("{}", name), "Иванов Иван Иванович");
+
+        let payment = Payment::builder(required())
+            .with_additional_requisites(name.requisites::<NoCustomRequisites>())
+            .build();
+
+        assert_eq!(payment.get("MiddleName"), Some("Иванович"));
+        assert_eq!(payment.payer_name(), Some(name));
+
+        let name = PayerName::new("Смит", "Джон", None).unwrap();
+        assert_eq!(
+            name.requisites::<NoCustomRequisites>(),
+            [
+                Requisite::LastName("Смит".into()),
+                Requisite::FirstName("Джон".into())
+            ]
+        );
+    }
+}
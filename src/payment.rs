@@ -1,4 +1,7 @@
-use core::fmt::{Debug, Display};
+use core::{
+    fmt::{Debug, Display},
+    str::FromStr,
+};
 
 use alloc::{
     boxed::Box,
@@ -8,11 +11,11 @@ use alloc::{
 };
 use encoding::Encoding;
 
-use crate::{LooseParser, PaymentParser, RequisiteToleranceParser, StrictParser};
+use crate::{LooseParser, PaymentParser, RequisiteToleranceParser, RoundTripParser, StrictParser};
 
 use super::{
     string_types::{ExactSizeString, MaxSizeString, StringExt},
-    CustomRequisites, NoCustomRequisites,
+    CustomRequisites, LengthUnit, NoCustomRequisites, Version, VersionRegistry,
 };
 
 pub(super) const FORMAT_ID_BYTES: [u8; 2] = [b'S', b'T'];
@@ -23,17 +26,52 @@ pub(super) const VERSION_0001_BYTES: [u8; 4] = [b'0', b'0', b'0', b'1'];
 pub struct Payment<T: CustomRequisites = NoCustomRequisites> {
     pub(super) header: PaymentHeader,
     pub(super) requisites: Vec<Requisite<T>>,
+
+    /// Сегменты тела платежа в исходном порядке, заполняются парсером точного воспроизведения.
+    ///
+    /// Реквизиты подставляются вместо [`RawSegment::Requisite`] по порядку,
+    /// реквизиты сверх разметки записываются в конце.
+    pub(super) layout: Vec<RawSegment>,
+}
+
+/// Сегмент разметки тела платежа.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum RawSegment {
+    /// Место очередного реквизита.
+    Requisite,
+
+    /// Сегмент, который не удалось разобрать.
+    Unparsed(Box<str>),
 }
 
 #[derive(Debug)]
 pub struct PaymentBuilder<T: CustomRequisites = NoCustomRequisites> {
     payment: Payment<T>,
+    version: Version,
+    length_unit: LengthUnit,
 }
 
 impl<T: CustomRequisites> PaymentBuilder<T> {
     /// Установка версии.
-    pub fn with_version(mut self, version: [u8; 4]) -> Self {
-        self.payment.header.version = version;
+    ///
+    /// Версия должна быть известна библиотеке, иначе возвращается ошибка [`crate::Error::UnsupportedVersion`].
+    pub fn with_version(self, version: [u8; 4]) -> super::Result<Self> {
+        let known = VersionRegistry::default();
+
+        let Some(spec) = known.get(version) else {
+            return Err(super::Error::UnsupportedVersion {
+                passed: version,
+                supported: known.ids().collect(),
+            });
+        };
+
+        Ok(self.with_version_spec(*spec))
+    }
+
+    /// Установка версии с пользовательским описанием.
+    pub fn with_version_spec(mut self, version: Version) -> Self {
+        self.payment.header.version = version.id();
+        self.version = version;
         self
     }
 
@@ -45,9 +83,10 @@ impl<T: CustomRequisites> PaymentBuilder<T> {
 
     /// Установка разделителя.
     pub fn with_separator(mut self, separator: char) -> Self {
-        assert!(separator.is_ascii());
-
-        self.payment.header.separator = separator as u8;
+        self.payment
+            .header
+            .set_separator(separator)
+            .expect("Недопустимый разделитель");
         self
     }
 
@@ -68,10 +107,34 @@ impl<T: CustomRequisites> PaymentBuilder<T> {
         self
     }
 
+    /// Единица измерения длины реквизитов для [`PaymentBuilder::try_build`].
+    ///
+    /// [`PaymentBuilder::build`] не проверяет реквизиты по правилам версии, поэтому единица измерения в нем не учитывается.
+    pub fn with_length_unit(mut self, length_unit: LengthUnit) -> Self {
+        self.length_unit = length_unit;
+        self
+    }
+
     /// Получение структуры с информацией о платеже.
+    ///
+    /// Реквизиты не проверяются по правилам версии, для проверки используется [`PaymentBuilder::try_build`].
     pub fn build(self) -> Payment<T> {
         self.payment
     }
+
+    /// Получение структуры с информацией о платеже с проверкой реквизитов по правилам версии.
+    ///
+    /// Длина значений измеряется в единицах, заданных [`PaymentBuilder::with_length_unit`], для кодировки платежа.
+    pub fn try_build(self) -> super::Result<Payment<T>> {
+        let encoding = self.payment.header.encoding;
+
+        self.payment.requisites.iter().try_for_each(|requisite| {
+            self.version
+                .validate_with(requisite, self.length_unit, encoding)
+        })?;
+
+        Ok(self.payment)
+    }
 }
 
 impl<T: CustomRequisites> Default for PaymentBuilder<T> {
@@ -85,7 +148,10 @@ impl<T: CustomRequisites> Default for PaymentBuilder<T> {
                     separator: b'|',
                 },
                 requisites: Vec::with_capacity(16),
+                layout: Vec::new(),
             },
+            version: Version::V0001,
+            length_unit: LengthUnit::default(),
         }
     }
 }
@@ -122,6 +188,14 @@ impl Payment {
     pub fn loose_parser() -> PaymentParser<LooseParser> {
         PaymentParser::default()
     }
+
+    /// Парсер, сохраняющий все сегменты для точного воспроизведения.
+    ///
+    /// Тело платежа декодируется строго: байты, недопустимые в кодировке платежа, приводят к ошибке
+    /// [`crate::Error::DecodingError`], так как их нельзя воспроизвести.
+    pub fn round_trip_parser() -> PaymentParser<RoundTripParser> {
+        PaymentParser::default()
+    }
 }
 
 impl<T: CustomRequisites> Payment<T> {
@@ -157,6 +231,11 @@ impl<T: CustomRequisites> Payment<T> {
         PaymentParser::default()
     }
 
+    /// Парсер с пользовательскими реквизитами, сохраняющий все сегменты для точного воспроизведения.
+    pub fn round_trip_custom_parser() -> PaymentParser<RoundTripParser, T> {
+        PaymentParser::default()
+    }
+
     /// Преобразования структуры в массив байтов согласно ГОСТ-56042.
     pub fn to_bytes(&self) -> super::Result<Vec<u8>> {
         let mut buffer = Vec::with_capacity(308);
@@ -166,21 +245,17 @@ impl<T: CustomRequisites> Payment<T> {
 
     /// Заполнение буфера информацией о платеже согласно ГОСТ-56042.
     pub fn write_to(&self, buffer: &mut Vec<u8>) -> super::Result<()> {
-        // Кодирование заголовка
-        buffer.push(self.header.format_id[0]);
-        buffer.push(self.header.format_id[1]);
-
-        buffer.push(self.header.version[0]);
-        buffer.push(self.header.version[1]);
-        buffer.push(self.header.version[2]);
-        buffer.push(self.header.version[3]);
-
-        buffer.push(self.header.encoding as u8);
+        // Кодирование заголовка, разделитель добавляется перед каждым реквизитом
+        buffer.extend(&self.header.to_bytes()[..7]);
 
         // Кодирование реквизитов
-        for requisite in &self.requisites {
+        for segment in self.segments() {
             buffer.push(self.header.separator);
-            buffer.extend(self.encode_requisite(requisite)?);
+
+            match segment {
+                Segment::Requisite(requisite) => buffer.extend(self.encode_requisite(requisite)?),
+                Segment::Unparsed(raw) => buffer.extend(self.encode_str(raw)?),
+            }
         }
 
         Ok(())
@@ -200,39 +275,126 @@ impl<T: CustomRequisites> Payment<T> {
             .map(|req| req.value())
     }
 
+    /// Получить значение по ключу, если реквизит отсутствует, то вернется ошибка.
+    pub(crate) fn get_required(&self, key: &str) -> super::Result<&str> {
+        self.get(key)
+            .ok_or_else(|| super::Error::MissingRequisite(key.into()))
+    }
+
     /// Получение заголовка
     pub fn header(&self) -> &PaymentHeader {
         &self.header
     }
 
+    /// Получение заголовка для изменения
+    pub fn header_mut(&mut self) -> &mut PaymentHeader {
+        &mut self.header
+    }
+
     /// Получение реквизитов
     pub fn requisites(&self) -> impl Iterator<Item = &Requisite<T>> {
         self.requisites.iter()
     }
+
+    /// Размеры значений реквизитов в символах и в байтах кодировки платежа.
+    pub fn requisite_sizes(&self) -> Vec<RequisiteSize<'_>> {
+        self.requisites
+            .iter()
+            .map(|requisite| RequisiteSize {
+                key: requisite.key(),
+                chars: requisite.value().chars().count(),
+                bytes: LengthUnit::EncodedBytes.measure(requisite.value(), self.header.encoding),
+            })
+            .collect()
+    }
+
+    /// Получение сегментов, которые не удалось разобрать в режиме точного воспроизведения.
+    pub fn unparsed_segments(&self) -> impl Iterator<Item = &str> {
+        self.layout.iter().filter_map(|segment| match segment {
+            RawSegment::Requisite => None,
+            RawSegment::Unparsed(raw) => Some(raw.as_ref()),
+        })
+    }
 }
 
 impl<T: CustomRequisites> Payment<T> {
+    /// Сегменты тела платежа: реквизиты, размещенные по разметке, и реквизиты сверх разметки.
+    ///
+    /// Если реквизитов меньше, чем мест в разметке, лишние места пропускаются.
+    fn segments(&self) -> impl Iterator<Item = Segment<'_, T>> {
+        let mut requisites = self.requisites.iter();
+        let mut layout = self.layout.iter();
+
+        core::iter::from_fn(move || loop {
+            match layout.next() {
+                Some(RawSegment::Unparsed(raw)) => return Some(Segment::Unparsed(raw)),
+                Some(RawSegment::Requisite) => {
+                    if let Some(requisite) = requisites.next() {
+                        return Some(Segment::Requisite(requisite));
+                    }
+                }
+                None => return requisites.next().map(Segment::Requisite),
+            }
+        })
+    }
+
+    /// Ключ с `=` или разделителем и значение с разделителем разбирались бы как другие сегменты.
     fn encode_requisite(&self, req: &Requisite<T>) -> super::Result<Vec<u8>> {
-        let pair = format!("{}={}", req.key(), req.value());
-        match self.header.encoding {
-            PaymentEncoding::Win1251 => encoding::all::WINDOWS_1251
-                .encode(&pair, encoding::EncoderTrap::Strict)
-                .map_err(|_| super::Error::EncodingError),
-            PaymentEncoding::Utf8 => Ok(pair.into_bytes()),
-            PaymentEncoding::Koi8R => encoding::all::KOI8_R
-                .encode(&pair, encoding::EncoderTrap::Strict)
-                .map_err(|_| super::Error::EncodingError),
+        let separator = self.header.separator();
+        if req.key().contains(['=', separator]) || req.value().contains(separator) {
+            return Err(super::Error::WrongPair(
+                req.key().into(),
+                req.value().into(),
+            ));
         }
+
+        self.encode_segment(req.key(), &format!("{}={}", req.key(), req.value()))
+    }
+
+    fn encode_str(&self, val: &str) -> super::Result<Vec<u8>> {
+        let key = val.split_once('=').map_or(val, |(key, _)| key);
+        self.encode_segment(key, val)
     }
+
+    fn encode_segment(&self, key: &str, val: &str) -> super::Result<Vec<u8>> {
+        let encoding = self.header.encoding;
+
+        encoding
+            .encode(val)
+            .ok_or_else(|| encoding.unencodable_char(key, val))
+    }
+}
+
+/// Размер значения реквизита.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RequisiteSize<'a> {
+    /// Ключ реквизита.
+    pub key: &'a str,
+
+    /// Длина в символах.
+    pub chars: usize,
+
+    /// Длина в байтах кодировки платежа, `None`, если значение нельзя записать в кодировке.
+    pub bytes: Option<usize>,
+}
+
+/// Сегмент тела платежа.
+enum Segment<'a, T: CustomRequisites> {
+    Requisite(&'a Requisite<T>),
+    Unparsed(&'a str),
 }
 
 impl<T: CustomRequisites> Display for Payment<T> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         Display::fmt(&self.header, f)?;
 
-        for req in self.requisites.iter() {
+        for segment in self.segments() {
             write!(f, "{}", self.header.separator())?;
-            Display::fmt(req, f)?;
+
+            match segment {
+                Segment::Requisite(req) => Display::fmt(req, f)?,
+                Segment::Unparsed(raw) => f.write_str(raw)?,
+            }
         }
 
         Ok(())
@@ -256,6 +418,27 @@ pub struct PaymentHeader {
 }
 
 impl PaymentHeader {
+    /// Создание заголовка с проверкой версии и разделителя.
+    ///
+    /// Версия должна состоять из четырех цифр, разделитель должен быть ASCII символом и не должен быть равен `=`.
+    pub fn new(
+        version: [u8; 4],
+        encoding: PaymentEncoding,
+        separator: char,
+    ) -> super::Result<Self> {
+        let mut header = Self {
+            format_id: FORMAT_ID_BYTES,
+            version: VERSION_0001_BYTES,
+            encoding,
+            separator: b'|',
+        };
+
+        header.set_version(version)?;
+        header.set_separator(separator)?;
+
+        Ok(header)
+    }
+
     /// Идентификатор формата
     pub fn format_id(&self) -> [char; 2] {
         [self.format_id[0] as char, self.format_id[1] as char]
@@ -271,15 +454,95 @@ impl PaymentHeader {
         ]
     }
 
-    /// Версия стандарта
+    /// Признак набора кодированных знаков
     pub fn encoding(&self) -> PaymentEncoding {
         self.encoding
     }
 
-    /// Версия стандарта
+    /// Разделитель
     pub fn separator(&self) -> char {
         self.separator as char
     }
+
+    /// Установка версии стандарта.
+    pub fn set_version(&mut self, version: [u8; 4]) -> super::Result<()> {
+        if !version.iter().all(u8::is_ascii_digit) {
+            return Err(super::Error::CorruptedHeader(
+                format!(
+                    "Версия должна состоять из цифр, установлена {}",
+                    String::from_utf8_lossy(&version)
+                )
+                .into(),
+            ));
+        }
+
+        self.version = version;
+        Ok(())
+    }
+
+    /// Установка признака набора кодированных знаков.
+    pub fn set_encoding(&mut self, encoding: PaymentEncoding) {
+        self.encoding = encoding;
+    }
+
+    /// Установка разделителя.
+    ///
+    /// Разделителем может быть только знак пунктуации ASCII, кроме `=`.
+    pub fn set_separator(&mut self, separator: char) -> super::Result<()> {
+        if !separator.is_ascii_punctuation() || separator == '=' {
+            return Err(super::Error::CorruptedHeader(
+                format!("Недопустимый разделитель {:?}", separator).into(),
+            ));
+        }
+
+        self.separator = separator as u8;
+        Ok(())
+    }
+
+    /// Преобразование заголовка в байты.
+    ///
+    /// Результат симметричен `TryFrom<&[u8]>`.
+    pub fn to_bytes(&self) -> [u8; 8] {
+        [
+            self.format_id[0],
+            self.format_id[1],
+            self.version[0],
+            self.version[1],
+            self.version[2],
+            self.version[3],
+            self.encoding as u8,
+            self.separator,
+        ]
+    }
+}
+
+impl TryFrom<&[u8]> for PaymentHeader {
+    type Error = super::Error;
+
+    fn try_from(bytes: &[u8]) -> super::Result<Self> {
+        if bytes.len() != 8 {
+            return Err(super::Error::CorruptedHeader(
+                format!("Длина заголовка должна быть 8, передано {}", bytes.len()).into(),
+            ));
+        }
+
+        if bytes[0..2] != FORMAT_ID_BYTES {
+            return Err(super::Error::WrongFormatId([bytes[0], bytes[1]]));
+        }
+
+        let version = [bytes[2], bytes[3], bytes[4], bytes[5]];
+        let encoding = bytes[6].try_into()?;
+
+        Self::new(version, encoding, bytes[7] as char)
+    }
+}
+
+impl FromStr for PaymentHeader {
+    type Err = super::Error;
+
+    fn from_str(s: &str) -> super::Result<Self> {
+        s.as_bytes().try_into()
+    }
 }
 
 impl Display for PaymentHeader {
@@ -308,6 +571,35 @@ pub struct RequiredRequisite {
     pub correstp_acc: MaxSizeString<20>,
 }
 
+impl RequiredRequisite {
+    /// Создание требуемых реквизитов с проверкой длины значений.
+    pub fn new(
+        name: &str,
+        personal_acc: &str,
+        bank_name: &str,
+        bic: &str,
+        correstp_acc: &str,
+    ) -> super::Result<Self> {
+        Ok(Self {
+            name: name
+                .to_max_size()
+                .ok_or_else(|| super::Error::WrongPair("Name".into(), name.into()))?,
+            personal_acc: personal_acc.to_exact_size().ok_or_else(|| {
+                super::Error::WrongPair("PersonalAcc".into(), personal_acc.into())
+            })?,
+            bank_name: bank_name
+                .to_max_size()
+                .ok_or_else(|| super::Error::WrongPair("BankName".into(), bank_name.into()))?,
+            bic: bic
+                .to_exact_size()
+                .ok_or_else(|| super::Error::WrongPair("BIC".into(), bic.into()))?,
+            correstp_acc: correstp_acc
+                .to_max_size()
+                .ok_or_else(|| super::Error::WrongPair("CorrespAcc".into(), correstp_acc.into()))?,
+        })
+    }
+}
+
 /// Варианты реквизитов.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Requisite<T: CustomRequisites> {
@@ -525,6 +817,73 @@ impl<T: CustomRequisites> Requisite<T> {
         }
     }
 
+    /// Описание реквизита на русском языке.
+    ///
+    /// Для пользовательских реквизитов возвращается ключ.
+    pub fn description(&self) -> &str {
+        match self {
+            Requisite::Name(_) => "Наименование получателя платежа",
+            Requisite::PersonalAcc(_) => "Номер счета получателя платежа",
+            Requisite::BankName(_) => "Наименование банка получателя платежа",
+            Requisite::BIC(_) => "БИК",
+            Requisite::CorrespAcc(_) => "Номер кор./сч. банка получателя платежа",
+            Requisite::Sum(_) => "Сумма платежа, в копейках",
+            Requisite::Purpose(_) => "Наименование платежа (назначение)",
+            Requisite::PayeeINN(_) => "ИНН получателя платежа",
+            Requisite::PayerINN(_) => "ИНН плательщика",
+            Requisite::DrawerStatus(_) => "Статус составителя платежного документа",
+            Requisite::KPP(_) => "КПП получателя платежа",
+            Requisite::CBC(_) => "КБК",
+            Requisite::OKTMO(_) => {
+                "Общероссийский классификатор территорий муниципальных образований (ОКТМО)"
+            }
+            Requisite::PaytReason(_) => "Основание налогового платежа",
+            Requisite::TaxPeriod(_) => "Налоговый период",
+            Requisite::DocNo(_) => "Номер документа",
+            Requisite::DocDate(_) => "Дата документа",
+            Requisite::TaxPayKind(_) => "Тип платежа",
+            Requisite::LastName(_) => "Фамилия плательщика",
+            Requisite::FirstName(_) => "Имя плательщика",
+            Requisite::MiddleName(_) => "Отчество плательщика",
+            Requisite::PayerAddress(_) => "Адрес плательщика",
+            Requisite::PersonalAccount(_) => "Лицевой счет бюджетного получателя",
+            Requisite::DocIdx(_) => "Индекс платежного документа",
+            Requisite::PensAcc(_) => {
+                "№ лицевого счета в системе персонифицированного учета в ПФР - СНИЛС"
+            }
+            Requisite::Contract(_) => "Номер договора",
+            Requisite::PersAcc(_) => {
+                "Номер лицевого счета плательщика в организации (в системе учета ПУ)"
+            }
+            Requisite::Flat(_) => "Номер квартиры",
+            Requisite::Phone(_) => "Номер телефона",
+            Requisite::PayerIdType(_) => "Вид ДУЛ плательщика",
+            Requisite::PayerIdNum(_) => "Номер ДУЛ плательщика",
+            Requisite::ChildFio(_) => "Ф.И.О. ребенка/учащегося",
+            Requisite::BirthDate(_) => "Дата рождения",
+            Requisite::PaymTerm(_) => "Срок платежа/дата выставления счета",
+            Requisite::PaymPeriod(_) => "Период оплаты",
+            Requisite::Category(_) => "Вид платежа",
+            Requisite::ServiceName(_) => "Код услуги/название прибора учета",
+            Requisite::CounterId(_) => "Номер прибора учета",
+            Requisite::CounterVal(_) => "Показание прибора учета",
+            Requisite::QuittId(_) => "Номер извещения, начисления, счета",
+            Requisite::QuittDate(_) => "Дата извещения/начисления/счета/постановления (для ГИБДД)",
+            Requisite::InstNum(_) => "Номер учреждения (образовательного, медицинского)",
+            Requisite::ClassNum(_) => "Номер группы детсада/класса школы",
+            Requisite::SpecFio(_) => "ФИО преподавателя, специалиста, оказывающего услугу",
+            Requisite::AddAmount(_) => {
+                "Сумма страховки/дополнительной услуги/Сумма пени (в копейках)"
+            }
+            Requisite::RuleId(_) => "Номер постановления (для ГИБДД)",
+            Requisite::ExecId(_) => "Номер исполнительного производства",
+            Requisite::RegType(_) => "Код вида платежа (например, для платежей в адрес Росреестра)",
+            Requisite::UIN(_) => "Уникальный идентификатор начисления",
+            Requisite::TechCode(_) => "Технический код",
+            Requisite::Custom(custom) => custom.key(),
+        }
+    }
+
     pub fn value(&self) -> &str {
         match self {
             Requisite::Name(v) => v,
@@ -666,6 +1025,8 @@ impl<T: CustomRequisites> TryFrom<(&str, &str)> for Requisite<T> {
             "PersonalAccount" => Requisite::PersonalAccount(val.into()),
             "DocIdx" => Requisite::DocIdx(val.into()),
             "PensAcc" => Requisite::PensAcc(val.into()),
+            "Contract" => Requisite::Contract(val.into()),
+            "PersAcc" => Requisite::PersAcc(val.into()),
             "Flat" => Requisite::Flat(val.into()),
             "Phone" => Requisite::Phone(val.into()),
             "PayerIdType" => Requisite::PayerIdType(val.into()),
@@ -720,7 +1081,7 @@ pub enum TechCode {
     /// Услуги, оказываемые УФМС
     FMS,
 
-    // ПФР
+    /// ПФР
     PFR,
 
     /// Погашение кредитов
@@ -747,11 +1108,32 @@ pub enum TechCode {
     /// Благотворительные и общественные организации
     Charity,
 
-    ///  Прочие услуги
+    /// Прочие услуги
     Other,
 }
 
 impl TechCode {
+    /// Название категории платежа.
+    pub fn description(&self) -> &'static str {
+        match self {
+            TechCode::Mobile => "Мобильная связь, стационарный телефон",
+            TechCode::HousingAndUtilites => "Коммунальные услуги, ЖКХ",
+            TechCode::Taxes => "ГИБДД, налоги, пошлины, бюджетные платежи",
+            TechCode::SecurityServices => "Охранные услуги",
+            TechCode::FMS => "Услуги, оказываемые УФМС",
+            TechCode::PFR => "ПФР",
+            TechCode::LoanRepayments => "Погашение кредитов",
+            TechCode::EducationalInstitutions => "Образовательные учреждения",
+            TechCode::InternetTV => "Интернет и ТВ",
+            TechCode::Emoney => "Электронные деньги",
+            TechCode::Vacation => "Отдых и путешествия",
+            TechCode::InvestmentInsurance => "Инвестиции и страхование",
+            TechCode::SportHealth => "Спорт и здоровье",
+            TechCode::Charity => "Благотворительные и общественные организации",
+            TechCode::Other => "Прочие услуги",
+        }
+    }
+
     fn as_str(&self) -> &str {
         match self {
             TechCode::Mobile => "01",
@@ -821,6 +1203,36 @@ impl TryFrom<u8> for PaymentEncoding {
     }
 }
 
+impl PaymentEncoding {
+    /// Возможность записать символ в кодировке.
+    pub fn can_encode(&self, ch: char) -> bool {
+        self.encode(ch.encode_utf8(&mut [0; 4])).is_some()
+    }
+
+    /// Ошибка с первым символом значения, который нельзя записать в кодировке.
+    pub(crate) fn unencodable_char(&self, key: &str, val: &str) -> super::Error {
+        super::Error::UnencodableChar {
+            key: key.into(),
+            ch: val
+                .chars()
+                .find(|ch| !self.can_encode(*ch))
+                .unwrap_or_default(),
+        }
+    }
+
+    pub(crate) fn encode(&self, val: &str) -> Option<Vec<u8>> {
+        match self {
+            PaymentEncoding::Win1251 => encoding::all::WINDOWS_1251
+                .encode(val, encoding::EncoderTrap::Strict)
+                .ok(),
+            PaymentEncoding::Utf8 => Some(val.as_bytes().to_vec()),
+            PaymentEncoding::Koi8R => encoding::all::KOI8_R
+                .encode(val, encoding::EncoderTrap::Strict)
+                .ok(),
+        }
+    }
+}
+
 impl Display for PaymentEncoding {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
@@ -0,0 +1,491 @@
+//! Извещение по форме ПД-4 с QR-кодом платежа в формате PDF.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use printpdf::{
+    path::PaintMode, Color, Line, Mm, PdfDocument, PdfLayerReference, Point, Rect, Rgb,
+};
+use qrcode::{EcLevel, QrCode};
+
+use crate::{
+    amount::{format_rubles, parse_kopecks},
+    CustomRequisites, Payment,
+};
+
+/// Размеры листа A4.
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+
+/// Левый край и ширина бланка.
+const LEFT: f32 = 15.0;
+const WIDTH: f32 = 180.0;
+
+/// Верхний край и высота части бланка (извещение и квитанция).
+const TOP: f32 = 282.0;
+const PART_HEIGHT: f32 = 75.0;
+
+/// Ширина левой колонки с заголовком и QR-кодом.
+const LEFT_COLUMN: f32 = 50.0;
+
+/// Сторона QR-кода вместе с полями.
+const QR_SIZE: f32 = 42.0;
+
+/// Ширина поля QR-кода в модулях.
+const QR_QUIET_ZONE: usize = 4;
+
+/// Размер шрифта заголовков и подписей.
+const TITLE_FONT_SIZE: f32 = 10.0;
+const LABEL_FONT_SIZE: f32 = 8.0;
+
+/// Размеры шрифта реквизитов: если реквизиты не помещаются, шрифт уменьшается.
+const FONT_SIZES: [f32; 3] = [8.0, 7.0, 6.0];
+
+/// Межстрочный интервал в миллиметрах на пункт размера шрифта.
+const LINE_SPACING: f32 = 0.45;
+
+/// Ширина правой колонки в символах на пункт размера шрифта: при 8 pt в строку помещается 85 символов.
+const LINE_WIDTH: f32 = 680.0;
+
+/// Высота правой колонки, доступная для реквизитов.
+const LINES_HEIGHT: f32 = PART_HEIGHT - 10.0;
+
+impl<T: CustomRequisites> Payment<T> {
+    /// Извещение по форме ПД-4 в формате PDF.
+    ///
+    /// Бланк состоит из извещения с QR-кодом платежа и квитанции.
+    /// Встроенные шрифты PDF не содержат кириллицы, поэтому передается TrueType шрифт `font`.
+    ///
+    /// Если реквизиты не помещаются на бланке, шрифт уменьшается до 6 pt,
+    /// а при нехватке места и для него возвращается ошибка [`crate::Error::PdfError`].
+    pub fn to_pdf(&self, font: &[u8]) -> super::Result<Vec<u8>> {
+        let (font_size, lines) = self.notice_layout()?;
+
+        let qr = QrCode::with_error_correction_level(self.to_bytes()?, EcLevel::M)
+            .map_err(|err| super::Error::PdfError(format!("{}", err).into()))?;
+
+        let (doc, page, layer) =
+            PdfDocument::new("Извещение", Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Бланк");
+        let font = doc.add_external_font(font).map_err(pdf_error)?;
+        let layer = doc.get_page(page).get_layer(layer);
+
+        for (part, title) in ["Извещение", "Квитанция"].into_iter().enumerate() {
+            let top = TOP - PART_HEIGHT * part as f32;
+
+            draw_frame(&layer, top);
+            layer.use_text(title, TITLE_FONT_SIZE, Mm(LEFT + 3.0), Mm(top - 6.0), &font);
+            layer.use_text(
+                "Кассир",
+                LABEL_FONT_SIZE,
+                Mm(LEFT + 3.0),
+                Mm(top - PART_HEIGHT + 4.0),
+                &font,
+            );
+
+            if part == 0 {
+                draw_qr(
+                    &layer,
+                    &qr,
+                    LEFT + (LEFT_COLUMN - QR_SIZE) / 2.0,
+                    top - 10.0,
+                );
+            }
+
+            for (idx, line) in lines.iter().enumerate() {
+                layer.use_text(
+                    line.as_str(),
+                    font_size,
+                    Mm(LEFT + LEFT_COLUMN + 3.0),
+                    Mm(top - 5.0 - font_size * LINE_SPACING * idx as f32),
+                    &font,
+                );
+            }
+
+            layer.use_text(
+                "Подпись плательщика ____________________",
+                LABEL_FONT_SIZE,
+                Mm(LEFT + LEFT_COLUMN + 3.0),
+                Mm(top - PART_HEIGHT + 4.0),
+                &font,
+            );
+        }
+
+        doc.save_to_bytes().map_err(pdf_error)
+    }
+
+    /// Запись извещения по форме ПД-4 в файл.
+    pub fn save_pdf(&self, path: impl AsRef<std::path::Path>, font: &[u8]) -> super::Result<()> {
+        std::fs::write(path, self.to_pdf(font)?)
+            .map_err(|err| super::Error::IoError(format!("{}", err).into()))
+    }
+
+    /// Наибольший размер шрифта, при котором все строки бланка помещаются в колонку, и сами строки.
+    fn notice_layout(&self) -> super::Result<(f32, Vec<String>)> {
+        FONT_SIZES
+            .into_iter()
+            .map(|font_size| {
+                let lines = self.notice_lines((LINE_WIDTH / font_size) as usize);
+                (font_size, lines)
+            })
+            .find(|(font_size, lines)| {
+                lines.len() <= (LINES_HEIGHT / (font_size * LINE_SPACING)) as usize
+            })
+            .ok_or_else(|| {
+                super::Error::PdfError("Реквизиты платежа не помещаются на бланке".into())
+            })
+    }
+
+    /// Строки бланка: получатель, реквизиты бюджетного платежа, назначение, плательщик и сумма.
+    fn notice_lines(&self, max_chars: usize) -> Vec<String> {
+        let field = |label: &str, key: &str| self.get(key).map(|val| format!("{}: {}", label, val));
+        let pair = |first: Option<String>, second: Option<String>| match (first, second) {
+            (Some(first), Some(second)) => Some(format!("{}, {}", first, second)),
+            (first, second) => first.or(second),
+        };
+
+        let payer = self.payer_name().map(|name| name.to_string()).or_else(|| {
+            self.get("PersAcc")
+                .map(|acc| format!("лицевой счет {}", acc))
+        });
+
+        let sum = self.get("Sum").map(|sum| match parse_kopecks(sum) {
+            Some(kopecks) => format!("{} руб.", format_rubles(kopecks, ',', Some(' '))),
+            None => sum.into(),
+        });
+
+        let fields = [
+            field("Получатель", "Name"),
+            pair(field("ИНН", "PayeeINN"), field("КПП", "KPP")),
+            field("Счёт", "PersonalAcc"),
+            field("Банк получателя", "BankName"),
+            pair(field("БИК", "BIC"), field("Корр. счёт", "CorrespAcc")),
+            pair(field("КБК", "CBC"), field("ОКТМО", "OKTMO")),
+            field("УИН", "UIN"),
+            field("Назначение платежа", "Purpose"),
+            payer.map(|payer| format!("Плательщик: {}", payer)),
+            field("Адрес плательщика", "PayerAddress"),
+            sum.map(|sum| format!("Сумма: {}", sum)),
+        ];
+
+        fields
+            .into_iter()
+            .flatten()
+            .flat_map(|field| wrap(&field, max_chars))
+            .collect()
+    }
+}
+
+/// Рамка части бланка и разделитель колонок.
+fn draw_frame(layer: &PdfLayerReference, top: f32) {
+    let bottom = top - PART_HEIGHT;
+    let column = LEFT + LEFT_COLUMN;
+
+    layer.set_outline_thickness(0.5);
+    layer.add_rect(
+        Rect::new(Mm(LEFT), Mm(bottom), Mm(LEFT + WIDTH), Mm(top)).with_mode(PaintMode::Stroke),
+    );
+    layer.add_line(Line {
+        points: alloc::vec![
+            (Point::new(Mm(column), Mm(bottom)), false),
+            (Point::new(Mm(column), Mm(top)), false),
+        ],
+        is_closed: false,
+    });
+}
+
+/// QR-код с верхним левым углом в точке (`left`, `top`).
+fn draw_qr(layer: &PdfLayerReference, qr: &QrCode, left: f32, top: f32) {
+    let width = qr.width();
+    let module = QR_SIZE / (width + QR_QUIET_ZONE * 2) as f32;
+    let offset = module * QR_QUIET_ZONE as f32;
+
+    layer.set_fill_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
+
+    for (idx, color) in qr.to_colors().into_iter().enumerate() {
+        if color != qrcode::Color::Dark {
+            continue;
+        }
+
+        let x = left + offset + module * (idx % width) as f32;
+        let y = top - offset - module * (idx / width) as f32;
+
+        layer.add_rect(
+            Rect::new(Mm(x), Mm(y - module), Mm(x + module), Mm(y)).with_mode(PaintMode::Fill),
+        );
+    }
+}
+
+/// Перенос строки по словам, слова длиннее строки разбиваются.
+fn wrap(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut line_chars = 0;
+
+    for word in text.split_whitespace() {
+        let mut word = word;
+
+        loop {
+            let word_chars = word.chars().count();
+            let space = usize::from(line_chars > 0);
+
+            if line_chars + space + word_chars <= max_chars {
+                if space > 0 {
+                    line.push(' ');
+                }
+                line.push_str(word);
+                line_chars += space + word_chars;
+                break;
+            }
+
+            if line_chars > 0 {
+                lines.push(core::mem::take(&mut line));
+                line_chars = 0;
+                continue;
+            }
+
+            let split = word
+                .char_indices()
+                .nth(max_chars)
+                .map_or(word.len(), |(idx, _)| idx);
+            lines.push(word[..split].into());
+            word = &word[split..];
+        }
+    }
+
+    if line_chars > 0 {
+        lines.push(line);
+    }
+
+    lines
+}
+
+fn pdf_error(err: printpdf::Error) -> super::Error {
+    super::Error::PdfError(format!("{}", err).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec::Vec};
+
+    use super::wrap;
+    use crate::{fixtures, Error, Payment, Requisite, StringExt};
+
+    fn payment() -> Payment {
+        fixtures::payment([
+            Requisite::PayeeINN("7707089101".to_max_size().unwrap()),
+            Requisite::Purpose("Оплата по договору № 15".to_max_size().unwrap()),
+            Requisite::LastName("Иванов".into()),
+            Requisite::FirstName("Иван".into()),
+            Requisite::Sum("123456789".to_max_size().unwrap()),
+        ])
+    }
+
+    /// Минимальный TrueType шрифт: латиница, кириллица и знаки препинания отображаются прямоугольником.
+    fn test_font() -> Vec<u8> {
+        let u16s = |values: &[u16]| {
+            values
+                .iter()
+                .flat_map(|v| v.to_be_bytes())
+                .collect::<Vec<_>>()
+        };
+        let u32s = |values: &[u32]| {
+            values
+                .iter()
+                .flat_map(|v| v.to_be_bytes())
+                .collect::<Vec<_>>()
+        };
+
+        // Формат 13: диапазоны символов отображаются в один глиф
+        let groups = [
+            (0x20, 0x7e),
+            (0xa0, 0xff),
+            (0x400, 0x45f),
+            (0x2010, 0x2026),
+            (0x2116, 0x2116),
+        ];
+        let mut cmap = [u16s(&[0, 1, 0, 4]), u32s(&[12]), u16s(&[13, 0])].concat();
+        cmap.extend(u32s(&[
+            16 + 12 * groups.len() as u32,
+            0,
+            groups.len() as u32,
+        ]));
+        for (start, end) in groups {
+            cmap.extend(u32s(&[start, end, 1]));
+        }
+
+        // Прямоугольник из четырех точек на кривой
+        let glyf = u16s(&[
+            1,
+            50,
+            0,
+            550,
+            700,
+            3,
+            0,
+            0x0101,
+            0x0101,
+            50,
+            500,
+            0,
+            -500i16 as u16,
+            0,
+            0,
+            700,
+            0,
+        ]);
+
+        let head = [
+            u32s(&[0x0001_0000, 0x0001_0000, 0, 0x5f0f_3cf5]),
+            u16s(&[0, 1000]),
+            alloc::vec![0; 16],
+            u16s(&[50, 0, 550, 700, 0, 8, 2, 0, 0]),
+        ]
+        .concat();
+        let hhea = [
+            u32s(&[0x0001_0000]),
+            u16s(&[
+                800,
+                -200i16 as u16,
+                0,
+                600,
+                0,
+                50,
+                550,
+                1,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                2,
+            ]),
+        ]
+        .concat();
+
+        let tables: [(&[u8; 4], Vec<u8>); 7] = [
+            (b"cmap", cmap),
+            (b"glyf", glyf),
+            (b"head", head),
+            (b"hhea", hhea),
+            (b"hmtx", u16s(&[600, 0, 600, 50])),
+            (b"loca", u16s(&[0, 0, 17])),
+            (b"maxp", [u32s(&[0x0000_5000]), u16s(&[2])].concat()),
+        ];
+
+        let mut font = [u32s(&[0x0001_0000]), u16s(&[7, 64, 2, 48])].concat();
+        let mut data = Vec::new();
+        for (tag, table) in &tables {
+            let offset = 12 + 16 * tables.len() + data.len();
+            font.extend(*tag);
+            font.extend(u32s(&[0, offset as u32, table.len() as u32]));
+            data.extend(table);
+            data.resize(data.len().next_multiple_of(4), 0);
+        }
+        font.extend(data);
+
+        font
+    }
+
+    #[test]
+    fn notice_lines_test() {
+        assert_eq!(
+            payment().notice_lines(85),
+            [
+                "Получатель: ООО «Три кита»",
+                "ИНН: 7707089101",
+                "Счёт: 40702810138250123017",
+                "Банк получателя: ОАО \"БАНК\"",
+                "БИК: 044525225, Корр. счёт: 30101810400000000225",
+                "Назначение платежа: Оплата по договору № 15",
+                "Плательщик: Иванов Иван",
+                "Сумма: 1 234 567,89 руб.",
+            ]
+        );
+    }
+
+    #[test]
+    fn wrap_test() {
+        assert_eq!(wrap("Оплата  по договору", 10), ["Оплата по", "договору"]);
+        assert_eq!(
+            wrap("40702810138250123017", 8),
+            ["40702810", "13825012", "3017"]
+        );
+        assert_eq!(wrap("", 8), [""; 0]);
+    }
+
+    #[test]
+    fn notice_layout_test() {
+        assert_eq!(payment().notice_layout().unwrap().0, 8.0);
+
+        let address = "г. Москва, ул. Ленина, д. 1, кв. 15; ".repeat(40);
+        let with_address = |address: &str| {
+            let mut payment = payment();
+            payment
+                .requisites
+                .push(Requisite::PayerAddress(address.into()));
+            payment
+        };
+
+        let payment = with_address(&address);
+        let (font_size, lines) = payment.notice_layout().unwrap();
+        assert_eq!(font_size, 6.0);
+        assert_eq!(
+            lines.last().map(String::as_str),
+            Some("Сумма: 1 234 567,89 руб.")
+        );
+
+        let payment = with_address(&address.repeat(3));
+        assert_eq!(
+            payment.notice_layout(),
+            Err(Error::PdfError(
+                "Реквизиты платежа не помещаются на бланке".into()
+            ))
+        );
+        assert_eq!(
+            payment.to_pdf(&test_font()),
+            Err(Error::PdfError(
+                "Реквизиты платежа не помещаются на бланке".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn pdf_test() {
+        let pdf = payment().to_pdf(&test_font()).unwrap();
+        assert!(pdf.starts_with(b"%PDF-"));
+
+        let document = printpdf::lopdf::Document::load_mem(&pdf).unwrap();
+        let pages = document.get_pages();
+        assert_eq!(pages.len(), 1);
+
+        let content = document.get_and_decode_page_content(pages[&1]).unwrap();
+        let rects = content
+            .operations
+            .iter()
+            .filter(|operation| operation.operator == "re")
+            .count();
+        let texts = content
+            .operations
+            .iter()
+            .filter(|operation| operation.operator == "Tj" || operation.operator == "TJ")
+            .count();
+
+        // Две рамки и темные модули QR-кода
+        assert!(rects > 100);
+        // Заголовок, подписи и 8 строк реквизитов в каждой части бланка
+        assert_eq!(texts, 2 * (3 + 8));
+    }
+
+    #[test]
+    fn pdf_font_error_test() {
+        assert!(matches!(
+            payment().to_pdf(b"not a font"),
+            Err(Error::PdfError(_))
+        ));
+    }
+}
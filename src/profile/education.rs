@@ -0,0 +1,176 @@
+//! Оплата услуг образовательных учреждений (технический код `08`).
+
+use alloc::{boxed::Box, format, vec::Vec};
+
+use crate::{CustomRequisites, Payment, PaymentDate, PaymentProfile, Requisite, TechCode};
+
+/// Платеж в школу, детский сад или другое образовательное учреждение.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EducationPayment {
+    child_fio: Box<str>,
+    inst_num: Box<str>,
+    class_num: Option<Box<str>>,
+    spec_fio: Option<Box<str>>,
+    birth_date: Option<PaymentDate>,
+}
+
+impl EducationPayment {
+    /// Создание платежа за обучающегося в учреждении с номером `inst_num`.
+    pub fn new(child_fio: &str, inst_num: &str) -> crate::Result<Self> {
+        if child_fio.trim().is_empty() {
+            return Err(crate::Error::WrongPair("ChildFio".into(), child_fio.into()));
+        }
+
+        if inst_num.trim().is_empty() {
+            return Err(crate::Error::WrongPair("InstNum".into(), inst_num.into()));
+        }
+
+        Ok(Self {
+            child_fio: child_fio.into(),
+            inst_num: inst_num.into(),
+            class_num: None,
+            spec_fio: None,
+            birth_date: None,
+        })
+    }
+
+    /// Установка номера группы или класса.
+    pub fn with_class_num(mut self, class_num: &str) -> Self {
+        self.class_num = Some(class_num.into());
+        self
+    }
+
+    /// Установка ФИО специалиста (педагога).
+    pub fn with_spec_fio(mut self, spec_fio: &str) -> Self {
+        self.spec_fio = Some(spec_fio.into());
+        self
+    }
+
+    /// Установка даты рождения обучающегося.
+    pub fn with_birth_date(mut self, birth_date: PaymentDate) -> Self {
+        self.birth_date = Some(birth_date);
+        self
+    }
+
+    /// ФИО обучающегося.
+    pub fn child_fio(&self) -> &str {
+        &self.child_fio
+    }
+
+    /// Номер учреждения.
+    pub fn inst_num(&self) -> &str {
+        &self.inst_num
+    }
+
+    /// Номер группы или класса.
+    pub fn class_num(&self) -> Option<&str> {
+        self.class_num.as_deref()
+    }
+
+    /// ФИО специалиста (педагога).
+    pub fn spec_fio(&self) -> Option<&str> {
+        self.spec_fio.as_deref()
+    }
+
+    /// Дата рождения обучающегося.
+    pub fn birth_date(&self) -> Option<PaymentDate> {
+        self.birth_date
+    }
+}
+
+impl PaymentProfile for EducationPayment {
+    const TECH_CODE: Option<TechCode> = Some(TechCode::EducationalInstitutions);
+
+    const REQUIRED: &'static [&'static str] = &["ChildFio", "InstNum"];
+
+    const OPTIONAL: &'static [&'static str] = &["ClassNum", "SpecFio", "BirthDate"];
+
+    fn validate_field(key: &str, value: &str) -> crate::Result<()> {
+        match key {
+            "BirthDate" => super::date_value(key, value).map(|_| ()),
+            _ => Ok(()),
+        }
+    }
+
+    fn from_validated<T: CustomRequisites>(payment: &Payment<T>) -> crate::Result<Self> {
+        let mut education = Self::new(
+            payment.get_required("ChildFio")?,
+            payment.get_required("InstNum")?,
+        )?;
+
+        education.class_num = payment.get("ClassNum").map(Into::into);
+        education.spec_fio = payment.get("SpecFio").map(Into::into);
+        education.birth_date = payment
+            .get("BirthDate")
+            .map(|date| super::date_value("BirthDate", date))
+            .transpose()?;
+
+        Ok(education)
+    }
+
+    fn requisites<T: CustomRequisites>(&self) -> Vec<Requisite<T>> {
+        let mut requisites = Vec::with_capacity(5);
+
+        requisites.push(Requisite::ChildFio(self.child_fio.clone()));
+        requisites.push(Requisite::InstNum(self.inst_num.clone()));
+        requisites.extend(self.class_num.clone().map(Requisite::ClassNum));
+        requisites.extend(self.spec_fio.clone().map(Requisite::SpecFio));
+        requisites.extend(
+            self.birth_date
+                .map(|date| Requisite::BirthDate(format!("{}", date).into())),
+        );
+
+        requisites
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fixtures::required, EducationPayment, Error, NoCustomRequisites, ParserStrategy, Payment,
+        PaymentDate, PaymentProfile, Requisite,
+    };
+
+    #[test]
+    fn education_payment_test() {
+        let education = EducationPayment::new("Иванов Петр Сергеевич", "15")
+            .unwrap()
+            .with_class_num("3Б")
+            .with_birth_date(PaymentDate::new(1, 9, 2015).unwrap());
+
+        let payment = education.builder(required()).build();
+
+        assert_eq!(payment.get("TechCode"), Some("08"));
+        assert_eq!(payment.get("BirthDate"), Some("01.09.2015"));
+
+        let parsed = Payment::parser()
+            .parse_from_bytes(&payment.to_bytes().unwrap())
+            .unwrap();
+        assert_eq!(EducationPayment::from_payment(&parsed), Ok(education));
+    }
+
+    #[test]
+    fn education_payment_errors_test() {
+        let payment = |requisites: &[Requisite<NoCustomRequisites>]| {
+            Payment::builder(required())
+                .with_additional_requisites(EducationPayment::TECH_CODE.map(Requisite::TechCode))
+                .with_additional_requisites(requisites.iter().cloned())
+                .build()
+        };
+
+        assert_eq!(
+            EducationPayment::from_payment(&payment(&[Requisite::ChildFio(
+                "Иванов Петр Сергеевич".into()
+            )])),
+            Err(Error::MissingRequisite("InstNum".into()))
+        );
+        assert_eq!(
+            EducationPayment::from_payment(&payment(&[
+                Requisite::ChildFio("Иванов Петр Сергеевич".into()),
+                Requisite::InstNum("15".into()),
+                Requisite::BirthDate("2015-09-01".into()),
+            ])),
+            Err(Error::WrongPair("BirthDate".into(), "2015-09-01".into()))
+        );
+    }
+}
@@ -0,0 +1,338 @@
+//! Оплата штрафов ГИБДД (технический код `03`).
+
+use alloc::{boxed::Box, format, vec::Vec};
+
+use crate::{
+    amount::parse_kopecks, CustomRequisites, Payment, PaymentDate, PaymentProfile, Requisite,
+    StringExt, TechCode,
+};
+
+/// Получатель штрафа: администратор доходов и коды бюджетной классификации.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FineRecipient {
+    payee_inn: Box<str>,
+    kpp: Box<str>,
+    cbc: Box<str>,
+    oktmo: Box<str>,
+}
+
+impl FineRecipient {
+    /// Создание получателя.
+    ///
+    /// ИНН - 10 цифр с контрольной цифрой, КПП - 9 символов, КБК - 20 цифр, ОКТМО - 8 или 11 цифр.
+    pub fn new(payee_inn: &str, kpp: &str, cbc: &str, oktmo: &str) -> crate::Result<Self> {
+        FinePayment::validate_field("PayeeINN", payee_inn)?;
+        FinePayment::validate_field("KPP", kpp)?;
+        FinePayment::validate_field("CBC", cbc)?;
+        FinePayment::validate_field("OKTMO", oktmo)?;
+
+        Ok(Self {
+            payee_inn: payee_inn.into(),
+            kpp: kpp.into(),
+            cbc: cbc.into(),
+            oktmo: oktmo.into(),
+        })
+    }
+
+    /// ИНН получателя.
+    pub fn payee_inn(&self) -> &str {
+        &self.payee_inn
+    }
+
+    /// КПП получателя.
+    pub fn kpp(&self) -> &str {
+        &self.kpp
+    }
+
+    /// Код бюджетной классификации.
+    pub fn cbc(&self) -> &str {
+        &self.cbc
+    }
+
+    /// Код ОКТМО.
+    pub fn oktmo(&self) -> &str {
+        &self.oktmo
+    }
+}
+
+/// Наибольшая сумма в копейках: реквизит `Sum` содержит не более 18 цифр.
+const MAX_SUM: u64 = 999_999_999_999_999_999;
+
+/// Платеж по постановлению о штрафе ГИБДД.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FinePayment {
+    rule_id: Box<str>,
+    uin: Box<str>,
+    sum: u64,
+    recipient: FineRecipient,
+    quitt_date: Option<PaymentDate>,
+}
+
+impl FinePayment {
+    /// Создание платежа по номеру постановления и сумме в копейках.
+    ///
+    /// Номер постановления является УИН: 20 или 25 цифр с контрольной цифрой.
+    /// Сумма не может быть нулевой или длиннее 18 цифр.
+    pub fn new(resolution: &str, sum: u64, recipient: FineRecipient) -> crate::Result<Self> {
+        if !is_valid_uin(resolution) {
+            return Err(crate::Error::WrongPair("RuleId".into(), resolution.into()));
+        }
+
+        if sum == 0 || sum > MAX_SUM {
+            return Err(crate::Error::WrongPair(
+                "Sum".into(),
+                format!("{}", sum).into(),
+            ));
+        }
+
+        Ok(Self {
+            rule_id: resolution.into(),
+            uin: resolution.into(),
+            sum,
+            recipient,
+            quitt_date: None,
+        })
+    }
+
+    /// Установка даты постановления.
+    pub fn with_quitt_date(mut self, quitt_date: PaymentDate) -> Self {
+        self.quitt_date = Some(quitt_date);
+        self
+    }
+
+    /// Номер постановления.
+    pub fn rule_id(&self) -> &str {
+        &self.rule_id
+    }
+
+    /// Уникальный идентификатор начисления.
+    pub fn uin(&self) -> &str {
+        &self.uin
+    }
+
+    /// Сумма в копейках.
+    pub fn sum(&self) -> u64 {
+        self.sum
+    }
+
+    /// Получатель.
+    pub fn recipient(&self) -> &FineRecipient {
+        &self.recipient
+    }
+
+    /// Дата постановления.
+    pub fn quitt_date(&self) -> Option<PaymentDate> {
+        self.quitt_date
+    }
+}
+
+impl PaymentProfile for FinePayment {
+    const TECH_CODE: Option<TechCode> = Some(TechCode::Taxes);
+
+    const REQUIRED: &'static [&'static str] =
+        &["Sum", "PayeeINN", "KPP", "CBC", "OKTMO", "UIN", "RuleId"];
+
+    const OPTIONAL: &'static [&'static str] = &["QuittDate"];
+
+    fn validate_field(key: &str, value: &str) -> crate::Result<()> {
+        let valid = match key {
+            "Sum" => parse_kopecks(value).is_some_and(|sum| sum > 0),
+            "PayeeINN" => is_valid_inn(value),
+            "KPP" => value.len() == 9 && value.bytes().all(|b| b.is_ascii_alphanumeric()),
+            "CBC" => is_digits(value, &[20]),
+            "OKTMO" => is_digits(value, &[8, 11]),
+            "UIN" | "RuleId" => is_valid_uin(value),
+            "QuittDate" => PaymentDate::parse(value).is_some(),
+            _ => true,
+        };
+
+        if !valid {
+            return Err(crate::Error::WrongPair(key.into(), value.into()));
+        }
+
+        Ok(())
+    }
+
+    fn from_validated<T: CustomRequisites>(payment: &Payment<T>) -> crate::Result<Self> {
+        let sum = payment.get_required("Sum")?;
+        let sum =
+            parse_kopecks(sum).ok_or_else(|| crate::Error::WrongPair("Sum".into(), sum.into()))?;
+
+        let recipient = FineRecipient::new(
+            payment.get_required("PayeeINN")?,
+            payment.get_required("KPP")?,
+            payment.get_required("CBC")?,
+            payment.get_required("OKTMO")?,
+        )?;
+
+        let mut fine = Self::new(payment.get_required("RuleId")?, sum, recipient)?;
+        fine.uin = payment.get_required("UIN")?.into();
+        fine.quitt_date = payment
+            .get("QuittDate")
+            .map(|date| super::date_value("QuittDate", date))
+            .transpose()?;
+
+        Ok(fine)
+    }
+
+    fn requisites<T: CustomRequisites>(&self) -> Vec<Requisite<T>> {
+        let mut requisites = Vec::with_capacity(8);
+
+        // Длины суммы и реквизитов получателя проверены при создании платежа
+        requisites.extend(
+            [
+                format!("{}", self.sum).to_max_size().map(Requisite::Sum),
+                self.recipient
+                    .payee_inn
+                    .to_max_size()
+                    .map(Requisite::PayeeINN),
+                self.recipient.kpp.to_max_size().map(Requisite::KPP),
+                self.recipient.cbc.to_max_size().map(Requisite::CBC),
+                self.recipient.oktmo.to_max_size().map(Requisite::OKTMO),
+            ]
+            .into_iter()
+            .flatten(),
+        );
+        requisites.push(Requisite::UIN(self.uin.clone()));
+        requisites.push(Requisite::RuleId(self.rule_id.clone()));
+        requisites.extend(
+            self.quitt_date
+                .map(|date| Requisite::QuittDate(format!("{}", date).into())),
+        );
+
+        requisites
+    }
+}
+
+fn is_digits(val: &str, lens: &[usize]) -> bool {
+    lens.contains(&val.len()) && val.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Проверка контрольной цифры ИНН юридического лица.
+fn is_valid_inn(inn: &str) -> bool {
+    const WEIGHTS: [u32; 9] = [2, 4, 10, 3, 5, 9, 4, 6, 8];
+
+    if !is_digits(inn, &[10]) {
+        return false;
+    }
+
+    let digits = inn.bytes().map(|b| (b - b'0') as u32).collect::<Vec<_>>();
+    let sum = WEIGHTS.iter().zip(&digits).map(|(w, d)| w * d).sum::<u32>();
+
+    sum % 11 % 10 == digits[9]
+}
+
+/// Проверка контрольной цифры УИН (20 или 25 цифр).
+fn is_valid_uin(uin: &str) -> bool {
+    if !is_digits(uin, &[20, 25]) {
+        return false;
+    }
+
+    let digits = uin.bytes().map(|b| (b - b'0') as u32).collect::<Vec<_>>();
+    let (control, digits) = digits.split_last().unwrap_or((&0, &[]));
+
+    let remainder = |shift: usize| {
+        digits
+            .iter()
+            .enumerate()
+            .map(|(idx, digit)| digit * ((idx + shift) % 10 + 1) as u32)
+            .sum::<u32>()
+            % 11
+    };
+
+    let expected = match remainder(0) {
+        10 => match remainder(2) {
+            10 => 0,
+            remainder => remainder,
+        },
+        remainder => remainder,
+    };
+
+    expected == *control
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fixtures::treasury_required, Error, FinePayment, FineRecipient, ParserStrategy, Payment,
+        PaymentDate, PaymentProfile, Requisite,
+    };
+
+    fn recipient() -> FineRecipient {
+        FineRecipient::new(
+            "7707089101",
+            "770731005",
+            "18811601123010001140",
+            "45000000",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn fine_payment_test() {
+        let fine = FinePayment::new("18810177240001234565", 50000, recipient())
+            .unwrap()
+            .with_quitt_date(PaymentDate::new(15, 3, 2024).unwrap());
+
+        let payment = fine.builder(treasury_required()).build();
+
+        assert_eq!(
+            payment.to_utf8_lossy().unwrap(),
+            "ST00012|Name=УФК по г. Москве|PersonalAcc=03100643000000017300|BankName=ГУ Банка России по ЦФО//УФК по г. Москве|BIC=004525988|CorrespAcc=40102810545370000003|TechCode=03|Sum=50000|PayeeINN=7707089101|KPP=770731005|CBC=18811601123010001140|OKTMO=45000000|UIN=18810177240001234565|RuleId=18810177240001234565|QuittDate=15.03.2024"
+        );
+        assert_eq!(payment.validate_treasury(), Ok(()));
+
+        let parsed = Payment::parser()
+            .parse_from_bytes(&payment.to_bytes().unwrap())
+            .unwrap();
+        assert_eq!(FinePayment::from_payment(&parsed), Ok(fine));
+    }
+
+    #[test]
+    fn fine_payment_errors_test() {
+        assert_eq!(
+            FinePayment::new("18810177240001234566", 50000, recipient()),
+            Err(Error::WrongPair(
+                "RuleId".into(),
+                "18810177240001234566".into()
+            ))
+        );
+        assert!(FinePayment::new("1881017724000123456789016", 50000, recipient()).is_ok());
+        assert_eq!(
+            FinePayment::new("18810177240001234565", 0, recipient()),
+            Err(Error::WrongPair("Sum".into(), "0".into()))
+        );
+        assert!(
+            FinePayment::new("18810177240001234565", 999_999_999_999_999_999, recipient()).is_ok()
+        );
+        assert_eq!(
+            FinePayment::new(
+                "18810177240001234565",
+                1_000_000_000_000_000_000,
+                recipient()
+            ),
+            Err(Error::WrongPair("Sum".into(), "1000000000000000000".into()))
+        );
+        assert_eq!(
+            FineRecipient::new(
+                "7707089102",
+                "770731005",
+                "18811601123010001140",
+                "45000000"
+            ),
+            Err(Error::WrongPair("PayeeINN".into(), "7707089102".into()))
+        );
+        assert_eq!(
+            FineRecipient::new("7707089101", "770731005", "18811601123010001140", "450000"),
+            Err(Error::WrongPair("OKTMO".into(), "450000".into()))
+        );
+
+        let payment = Payment::builder(treasury_required())
+            .with_additional_requisites(FinePayment::TECH_CODE.map(Requisite::TechCode))
+            .build();
+        assert_eq!(
+            FinePayment::from_payment(&payment),
+            Err(Error::MissingRequisite("Sum".into()))
+        );
+    }
+}
@@ -0,0 +1,339 @@
+//! Оплата жилищно-коммунальных услуг (технический код `02`).
+
+use core::{fmt::Display, str::FromStr};
+
+use alloc::{boxed::Box, format, vec::Vec};
+
+use crate::{CustomRequisites, Payment, PaymentProfile, Requisite, TechCode};
+
+/// Период оплаты в формате `ММГГГГ`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PaymentPeriod {
+    year: u16,
+    month: u8,
+}
+
+impl PaymentPeriod {
+    /// Создание периода, месяц от 1 до 12, год от 1 до 9999.
+    pub fn new(month: u8, year: u16) -> crate::Result<Self> {
+        if !(1..=12).contains(&month) || !(1..=9999).contains(&year) {
+            return Err(crate::Error::WrongPair(
+                "PaymPeriod".into(),
+                format!("{:02}{:04}", month, year).into(),
+            ));
+        }
+
+        Ok(Self { year, month })
+    }
+
+    /// Месяц.
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Год.
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+}
+
+impl FromStr for PaymentPeriod {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        let wrong_period = || crate::Error::WrongPair("PaymPeriod".into(), s.into());
+
+        if s.len() != 6 || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(wrong_period());
+        }
+
+        let month = s[..2].parse().map_err(|_| wrong_period())?;
+        let year = s[2..].parse().map_err(|_| wrong_period())?;
+
+        Self::new(month, year).map_err(|_| wrong_period())
+    }
+}
+
+impl Display for PaymentPeriod {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:02}{:04}", self.month, self.year)
+    }
+}
+
+/// Показание прибора учета.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MeterReading {
+    counter_id: Box<str>,
+    value: Box<str>,
+}
+
+impl MeterReading {
+    /// Создание показания.
+    ///
+    /// Показание - неотрицательное число, дробная часть отделяется точкой или запятой.
+    pub fn new(counter_id: &str, value: &str) -> crate::Result<Self> {
+        if counter_id.trim().is_empty() {
+            return Err(crate::Error::WrongPair(
+                "CounterId".into(),
+                counter_id.into(),
+            ));
+        }
+
+        check_counter_val(value)?;
+
+        Ok(Self {
+            counter_id: counter_id.into(),
+            value: value.into(),
+        })
+    }
+
+    /// Номер прибора учета.
+    pub fn counter_id(&self) -> &str {
+        &self.counter_id
+    }
+
+    /// Показание прибора учета.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+/// Платеж за жилищно-коммунальные услуги.
+///
+/// Показания нескольких приборов учета передаются парами `CounterId` и `CounterVal` в порядке следования.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HousingPayment {
+    pers_acc: Box<str>,
+    period: PaymentPeriod,
+    flat: Option<Box<str>>,
+    service_name: Option<Box<str>>,
+    category: Option<Box<str>>,
+    readings: Vec<MeterReading>,
+}
+
+impl HousingPayment {
+    /// Создание платежа по лицевому счету плательщика за период.
+    pub fn new(pers_acc: &str, period: PaymentPeriod) -> crate::Result<Self> {
+        if pers_acc.trim().is_empty() {
+            return Err(crate::Error::WrongPair("PersAcc".into(), pers_acc.into()));
+        }
+
+        Ok(Self {
+            pers_acc: pers_acc.into(),
+            period,
+            flat: None,
+            service_name: None,
+            category: None,
+            readings: Vec::new(),
+        })
+    }
+
+    /// Установка номера квартиры.
+    pub fn with_flat(mut self, flat: &str) -> Self {
+        self.flat = Some(flat.into());
+        self
+    }
+
+    /// Установка кода услуги.
+    pub fn with_service_name(mut self, service_name: &str) -> Self {
+        self.service_name = Some(service_name.into());
+        self
+    }
+
+    /// Установка вида платежа.
+    pub fn with_category(mut self, category: &str) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    /// Добавление показания прибора учета.
+    pub fn with_reading(mut self, reading: MeterReading) -> Self {
+        self.readings.push(reading);
+        self
+    }
+
+    /// Лицевой счет плательщика.
+    pub fn pers_acc(&self) -> &str {
+        &self.pers_acc
+    }
+
+    /// Период оплаты.
+    pub fn period(&self) -> PaymentPeriod {
+        self.period
+    }
+
+    /// Номер квартиры.
+    pub fn flat(&self) -> Option<&str> {
+        self.flat.as_deref()
+    }
+
+    /// Код услуги.
+    pub fn service_name(&self) -> Option<&str> {
+        self.service_name.as_deref()
+    }
+
+    /// Вид платежа.
+    pub fn category(&self) -> Option<&str> {
+        self.category.as_deref()
+    }
+
+    /// Показания приборов учета.
+    pub fn readings(&self) -> &[MeterReading] {
+        &self.readings
+    }
+}
+
+impl PaymentProfile for HousingPayment {
+    const TECH_CODE: Option<TechCode> = Some(TechCode::HousingAndUtilites);
+
+    const REQUIRED: &'static [&'static str] = &["PersAcc", "PaymPeriod"];
+
+    const OPTIONAL: &'static [&'static str] =
+        &["Flat", "ServiceName", "Category", "CounterId", "CounterVal"];
+
+    fn validate_field(key: &str, value: &str) -> crate::Result<()> {
+        match key {
+            "PaymPeriod" => value.parse::<PaymentPeriod>().map(|_| ()),
+            "CounterVal" => check_counter_val(value),
+            _ => Ok(()),
+        }
+    }
+
+    /// Показания приборов учета собираются из пар `CounterId` и `CounterVal` в порядке следования.
+    fn from_validated<T: CustomRequisites>(payment: &Payment<T>) -> crate::Result<Self> {
+        let mut housing = Self::new(
+            payment.get_required("PersAcc")?,
+            payment.get_required("PaymPeriod")?.parse()?,
+        )?;
+
+        housing.flat = payment.get("Flat").map(Into::into);
+        housing.service_name = payment.get("ServiceName").map(Into::into);
+        housing.category = payment.get("Category").map(Into::into);
+
+        let mut counter_ids = super::values(payment, "CounterId");
+        let mut counter_vals = super::values(payment, "CounterVal");
+
+        loop {
+            match (counter_ids.next(), counter_vals.next()) {
+                (Some(counter_id), Some(value)) => {
+                    housing.readings.push(MeterReading::new(counter_id, value)?)
+                }
+                (Some(_), None) => return Err(crate::Error::MissingRequisite("CounterVal".into())),
+                (None, Some(_)) => return Err(crate::Error::MissingRequisite("CounterId".into())),
+                (None, None) => break,
+            }
+        }
+
+        Ok(housing)
+    }
+
+    fn requisites<T: CustomRequisites>(&self) -> Vec<Requisite<T>> {
+        let mut requisites = Vec::with_capacity(5 + self.readings.len() * 2);
+
+        requisites.push(Requisite::PersAcc(self.pers_acc.clone()));
+        requisites.push(Requisite::PaymPeriod(format!("{}", self.period).into()));
+        requisites.extend(self.flat.clone().map(Requisite::Flat));
+        requisites.extend(self.service_name.clone().map(Requisite::ServiceName));
+        requisites.extend(self.category.clone().map(Requisite::Category));
+
+        for reading in &self.readings {
+            requisites.push(Requisite::CounterId(reading.counter_id.clone()));
+            requisites.push(Requisite::CounterVal(reading.value.clone()));
+        }
+
+        requisites
+    }
+}
+
+/// Показание - неотрицательное число, дробная часть отделяется точкой или запятой.
+fn check_counter_val(value: &str) -> crate::Result<()> {
+    let (whole, fraction) = value.split_once(['.', ',']).unwrap_or((value, "0"));
+    let is_number = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+
+    if !is_number(whole) || !is_number(fraction) {
+        return Err(crate::Error::WrongPair("CounterVal".into(), value.into()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fixtures::required, Error, HousingPayment, MeterReading, ParserStrategy, Payment,
+        PaymentPeriod, PaymentProfile, Requisite, StringExt,
+    };
+
+    #[test]
+    fn payment_period_test() {
+        let period: PaymentPeriod = "032024".parse().unwrap();
+
+        assert_eq!(period.month(), 3);
+        assert_eq!(period.year(), 2024);
+        assert_eq!(alloc::format!("{}", period), "032024");
+
+        for wrong in ["132024", "002024", "32024", "03.2024", "03202４"] {
+            assert_eq!(
+                wrong.parse::<PaymentPeriod>(),
+                Err(Error::WrongPair("PaymPeriod".into(), wrong.into()))
+            );
+        }
+    }
+
+    #[test]
+    fn housing_payment_test() {
+        let housing = HousingPayment::new("1234567", PaymentPeriod::new(3, 2024).unwrap())
+            .unwrap()
+            .with_flat("15")
+            .with_reading(MeterReading::new("ХВС-001", "123,45").unwrap())
+            .with_reading(MeterReading::new("ГВС-002", "67").unwrap());
+
+        let payment = housing
+            .builder(required())
+            .with_additional_requisites([Requisite::Sum("350000".to_max_size().unwrap())])
+            .build();
+
+        assert_eq!(
+            payment.to_utf8_lossy().unwrap(),
+            "ST00012|Name=ООО «Три кита»|PersonalAcc=40702810138250123017|BankName=ОАО \"БАНК\"|BIC=044525225|CorrespAcc=30101810400000000225|TechCode=02|PersAcc=1234567|PaymPeriod=032024|Flat=15|CounterId=ХВС-001|CounterVal=123,45|CounterId=ГВС-002|CounterVal=67|Sum=350000"
+        );
+
+        let parsed = Payment::parser()
+            .parse_from_bytes(&payment.to_bytes().unwrap())
+            .unwrap();
+        assert_eq!(HousingPayment::from_payment(&parsed), Ok(housing));
+    }
+
+    #[test]
+    fn housing_payment_errors_test() {
+        assert_eq!(
+            MeterReading::new("1", "12.3.4"),
+            Err(Error::WrongPair("CounterVal".into(), "12.3.4".into()))
+        );
+
+        let payment = Payment::builder(required())
+            .with_additional_requisites([
+                Requisite::TechCode(crate::TechCode::HousingAndUtilites),
+                Requisite::PersAcc("1234567".into()),
+                Requisite::PaymPeriod("032024".into()),
+                Requisite::CounterId("1".into()),
+            ])
+            .build();
+        assert_eq!(
+            HousingPayment::from_payment(&payment),
+            Err(Error::MissingRequisite("CounterVal".into()))
+        );
+
+        let payment = Payment::builder(required())
+            .with_additional_requisites([
+                Requisite::TechCode(crate::TechCode::Taxes),
+                Requisite::PersAcc("1234567".into()),
+                Requisite::PaymPeriod("032024".into()),
+            ])
+            .build();
+        assert_eq!(
+            HousingPayment::from_payment(&payment),
+            Err(Error::WrongPair("TechCode".into(), "03".into()))
+        );
+    }
+}
@@ -0,0 +1,324 @@
+//! Профили платежей отдельных категорий: типизированные наборы дополнительных реквизитов.
+
+mod education;
+mod fine;
+mod housing;
+
+pub use education::*;
+pub use fine::*;
+pub use housing::*;
+
+use core::fmt::Display;
+
+use alloc::vec::Vec;
+
+use crate::{CustomRequisites, Payment, PaymentBuilder, RequiredRequisite, Requisite, TechCode};
+
+/// Схема дополнительных реквизитов для платежей отдельной категории.
+///
+/// Профиль объявляет обязательные и необязательные реквизиты, проверку их значений
+/// и преобразование платежа в типизированную структуру и обратно.
+pub trait PaymentProfile: Sized {
+    /// Технический код платежей профиля.
+    ///
+    /// Если код задан, платеж без реквизита `TechCode` не проходит проверку профиля.
+    const TECH_CODE: Option<TechCode>;
+
+    /// Ключи обязательных реквизитов.
+    const REQUIRED: &'static [&'static str];
+
+    /// Ключи необязательных реквизитов.
+    const OPTIONAL: &'static [&'static str];
+
+    /// Проверка значения реквизита профиля.
+    fn validate_field(key: &str, value: &str) -> super::Result<()> {
+        let _ = (key, value);
+        Ok(())
+    }
+
+    /// Создание структуры из платежа, прошедшего проверку [`PaymentProfile::validate`].
+    fn from_validated<T: CustomRequisites>(payment: &Payment<T>) -> super::Result<Self>;
+
+    /// Реквизиты профиля без технического кода.
+    fn requisites<T: CustomRequisites>(&self) -> Vec<Requisite<T>>;
+
+    /// Проверка платежа: технический код присутствует и совпадает с кодом профиля, если код профиля задан,
+    /// обязательные реквизиты присутствуют и не пусты, значения всех реквизитов профиля корректны.
+    fn validate<T: CustomRequisites>(payment: &Payment<T>) -> super::Result<()> {
+        if let Some(tech_code) = Self::TECH_CODE {
+            check_tech_code(payment, tech_code)?;
+        }
+
+        for key in Self::REQUIRED {
+            required_value(payment, key)?;
+        }
+
+        payment
+            .requisites()
+            .filter(|requisite| {
+                Self::REQUIRED.contains(&requisite.key())
+                    || Self::OPTIONAL.contains(&requisite.key())
+            })
+            .try_for_each(|requisite| Self::validate_field(requisite.key(), requisite.value()))
+    }
+
+    /// Чтение профиля из платежа.
+    fn from_payment<T: CustomRequisites>(payment: &Payment<T>) -> super::Result<Self> {
+        Self::validate(payment)?;
+        Self::from_validated(payment)
+    }
+
+    /// Строитель платежа с техническим кодом и реквизитами профиля.
+    fn builder(&self, required: RequiredRequisite) -> PaymentBuilder {
+        self.custom_builder(required)
+    }
+
+    /// Строитель платежа с пользовательскими реквизитами, техническим кодом и реквизитами профиля.
+    fn custom_builder<T: CustomRequisites>(
+        &self,
+        required: RequiredRequisite,
+    ) -> PaymentBuilder<T> {
+        Payment::custom_builder(required)
+            .with_additional_requisites(Self::TECH_CODE.map(Requisite::TechCode))
+            .with_additional_requisites(self.requisites())
+    }
+
+    /// Платеж с реквизитами профиля.
+    fn into_payment(self, required: RequiredRequisite) -> Payment {
+        self.builder(required).build()
+    }
+}
+
+impl<T: CustomRequisites> Payment<T> {
+    /// Чтение профиля из платежа.
+    pub fn as_profile<P: PaymentProfile>(&self) -> super::Result<P> {
+        P::from_payment(self)
+    }
+}
+
+/// Дата в формате `ДД.ММ.ГГГГ`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PaymentDate {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl PaymentDate {
+    /// Создание даты с проверкой числа дней в месяце.
+    pub fn new(day: u8, month: u8, year: u16) -> Option<Self> {
+        let leap =
+            year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+        let days = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => return None,
+        };
+
+        ((1..=days).contains(&day) && (1..=9999).contains(&year)).then_some(Self {
+            year,
+            month,
+            day,
+        })
+    }
+
+    /// Разбор даты в формате `ДД.ММ.ГГГГ`.
+    pub fn parse(val: &str) -> Option<Self> {
+        let bytes = val.as_bytes();
+        if bytes.len() != 10 || bytes[2] != b'.' || bytes[5] != b'.' {
+            return None;
+        }
+
+        let number = |range: core::ops::Range<usize>| {
+            let part = &val[range];
+            part.bytes()
+                .all(|b| b.is_ascii_digit())
+                .then(|| part.parse::<u16>().ok())
+                .flatten()
+        };
+
+        Self::new(number(0..2)? as u8, number(3..5)? as u8, number(6..10)?)
+    }
+
+    /// День.
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Месяц.
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Год.
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+}
+
+impl Display for PaymentDate {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:02}.{:02}.{:04}", self.day, self.month, self.year)
+    }
+}
+
+/// Проверка технического кода платежа.
+///
+/// Платеж без технического кода не подходит профилю, у которого код задан.
+fn check_tech_code<T: CustomRequisites>(
+    payment: &Payment<T>,
+    expected: TechCode,
+) -> super::Result<()> {
+    let tech_code = payment.requisites().find_map(|requisite| match requisite {
+        Requisite::TechCode(tech_code) => Some(tech_code),
+        _ => None,
+    });
+
+    match tech_code {
+        None => Err(super::Error::MissingRequisite("TechCode".into())),
+        Some(tech_code) if *tech_code != expected => Err(super::Error::WrongPair(
+            "TechCode".into(),
+            Requisite::<T>::TechCode(*tech_code).value().into(),
+        )),
+        Some(_) => Ok(()),
+    }
+}
+
+/// Непустое значение реквизита.
+fn required_value<'a, T: CustomRequisites>(
+    payment: &'a Payment<T>,
+    key: &str,
+) -> super::Result<&'a str> {
+    let value = payment.get_required(key)?;
+
+    if value.trim().is_empty() {
+        return Err(super::Error::WrongPair(key.into(), value.into()));
+    }
+
+    Ok(value)
+}
+
+/// Все значения реквизита, который может повторяться.
+fn values<'a, T: CustomRequisites>(
+    payment: &'a Payment<T>,
+    key: &'a str,
+) -> impl Iterator<Item = &'a str> {
+    payment
+        .requisites()
+        .filter(move |requisite| requisite.key() == key)
+        .map(Requisite::value)
+}
+
+/// Дата из значения реквизита.
+fn date_value(key: &str, value: &str) -> super::Result<PaymentDate> {
+    PaymentDate::parse(value).ok_or_else(|| super::Error::WrongPair(key.into(), value.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{boxed::Box, vec::Vec};
+
+    use crate::{
+        fixtures::required, CustomRequisites, Error, Payment, PaymentDate, PaymentProfile,
+        Requisite, TechCode,
+    };
+
+    /// Профиль, который определяет пользователь библиотеки.
+    #[derive(Clone, Debug, PartialEq)]
+    struct LoanPayment {
+        contract: Box<str>,
+        last_name: Option<Box<str>>,
+    }
+
+    impl PaymentProfile for LoanPayment {
+        const TECH_CODE: Option<TechCode> = Some(TechCode::LoanRepayments);
+        const REQUIRED: &'static [&'static str] = &["Contract"];
+        const OPTIONAL: &'static [&'static str] = &["LastName"];
+
+        fn validate_field(key: &str, value: &str) -> crate::Result<()> {
+            match key {
+                "Contract" if !value.starts_with("КД-") => {
+                    Err(Error::WrongPair(key.into(), value.into()))
+                }
+                _ => Ok(()),
+            }
+        }
+
+        fn from_validated<T: CustomRequisites>(payment: &Payment<T>) -> crate::Result<Self> {
+            Ok(Self {
+                contract: payment.get_required("Contract")?.into(),
+                last_name: payment.get("LastName").map(Into::into),
+            })
+        }
+
+        fn requisites<T: CustomRequisites>(&self) -> Vec<Requisite<T>> {
+            let mut requisites = alloc::vec![Requisite::Contract(self.contract.clone())];
+            requisites.extend(self.last_name.clone().map(Requisite::LastName));
+            requisites
+        }
+    }
+
+    #[test]
+    fn custom_profile_test() {
+        let loan = LoanPayment {
+            contract: "КД-15/2024".into(),
+            last_name: Some("Иванов".into()),
+        };
+
+        let payment = loan.clone().into_payment(required());
+
+        assert_eq!(payment.get("TechCode"), Some("07"));
+        assert_eq!(payment.as_profile::<LoanPayment>(), Ok(loan));
+
+        let payment = Payment::builder(required())
+            .with_additional_requisites([Requisite::Contract("КД-15/2024".into())])
+            .build();
+        assert_eq!(
+            payment.as_profile::<LoanPayment>(),
+            Err(Error::MissingRequisite("TechCode".into()))
+        );
+
+        let payment = Payment::builder(required())
+            .with_additional_requisites([
+                Requisite::TechCode(TechCode::LoanRepayments),
+                Requisite::Contract("15/2024".into()),
+            ])
+            .build();
+        assert_eq!(
+            payment.as_profile::<LoanPayment>(),
+            Err(Error::WrongPair("Contract".into(), "15/2024".into()))
+        );
+
+        let payment = Payment::builder(required())
+            .with_additional_requisites([
+                Requisite::TechCode(TechCode::LoanRepayments),
+                Requisite::LastName("Иванов".into()),
+            ])
+            .build();
+        assert_eq!(
+            payment.as_profile::<LoanPayment>(),
+            Err(Error::MissingRequisite("Contract".into()))
+        );
+    }
+
+    #[test]
+    fn payment_date_test() {
+        let date = PaymentDate::parse("29.02.2024").unwrap();
+
+        assert_eq!((date.day(), date.month(), date.year()), (29, 2, 2024));
+        assert_eq!(alloc::format!("{}", date), "29.02.2024");
+
+        for wrong in [
+            "29.02.2023",
+            "31.04.2024",
+            "1.01.2024",
+            "01-01-2024",
+            "00.01.2024",
+            "+1.01.2024",
+        ] {
+            assert_eq!(PaymentDate::parse(wrong), None);
+        }
+    }
+}
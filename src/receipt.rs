@@ -0,0 +1,160 @@
+//! Текстовая квитанция с реквизитами платежа.
+
+use core::fmt::Display;
+
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+
+use crate::{
+    amount::{format_rubles, parse_kopecks},
+    CustomRequisites, PayerIdType, Payment, Requisite,
+};
+
+/// Реквизиты платежа с подписями на русском языке.
+///
+/// Подписи обязательных реквизитов сокращены до привычных в квитанциях («Получатель», «Счёт», «БИК»),
+/// для остальных реквизитов используется их описание [`Requisite::description`].
+/// Суммы записываются в рублях, технический код и вид документа - названием.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Receipt {
+    lines: Vec<(Box<str>, Box<str>)>,
+}
+
+impl Receipt {
+    /// Строки квитанции: подпись и значение.
+    pub fn lines(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.lines
+            .iter()
+            .map(|(label, value)| (label.as_ref(), value.as_ref()))
+    }
+
+    /// Квитанция в виде текста, по строке на реквизит.
+    pub fn to_text(&self) -> String {
+        format!("{}", self)
+    }
+
+    /// Квитанция в виде HTML таблицы.
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<table class=\"payment-receipt\">\n");
+
+        for (label, value) in self.lines() {
+            html.push_str(&format!(
+                "  <tr><th>{}</th><td>{}</td></tr>\n",
+                escape_html(label),
+                escape_html(value)
+            ));
+        }
+
+        html.push_str("</table>\n");
+        html
+    }
+}
+
+impl Display for Receipt {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (label, value) in self.lines() {
+            writeln!(f, "{}: {}", label, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: CustomRequisites> Payment<T> {
+    /// Квитанция с реквизитами платежа.
+    pub fn receipt(&self) -> Receipt {
+        Receipt {
+            lines: self
+                .requisites()
+                .map(|requisite| (label(requisite).into(), value(requisite).into()))
+                .collect(),
+        }
+    }
+}
+
+fn label<T: CustomRequisites>(requisite: &Requisite<T>) -> &str {
+    match requisite {
+        Requisite::Name(_) => "Получатель",
+        Requisite::PersonalAcc(_) => "Счёт",
+        Requisite::BankName(_) => "Банк получателя",
+        Requisite::BIC(_) => "БИК",
+        Requisite::CorrespAcc(_) => "Корр. счёт",
+        Requisite::Sum(_) => "Сумма",
+        Requisite::Purpose(_) => "Назначение платежа",
+        Requisite::OKTMO(_) => "ОКТМО",
+        Requisite::AddAmount(_) => "Сумма страховки, дополнительной услуги или пени",
+        Requisite::TechCode(_) => "Категория платежа",
+        _ => requisite.description(),
+    }
+}
+
+fn value<T: CustomRequisites>(requisite: &Requisite<T>) -> String {
+    match requisite {
+        Requisite::Sum(_) | Requisite::AddAmount(_) => match parse_kopecks(requisite.value()) {
+            Some(kopecks) => format!("{} руб.", format_rubles(kopecks, ',', Some(' '))),
+            None => requisite.value().into(),
+        },
+        Requisite::TechCode(tech_code) => {
+            format!("{} ({})", tech_code.description(), requisite.value())
+        }
+        Requisite::PayerIdType(id_type) => match id_type.parse::<PayerIdType>() {
+            Ok(id_type) => id_type.description().into(),
+            Err(_) => requisite.value().into(),
+        },
+        _ => requisite.value().into(),
+    }
+}
+
+fn escape_html(val: &str) -> String {
+    let mut escaped = String::with_capacity(val.len());
+
+    for ch in val.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            ch => escaped.push(ch),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures::required, PayerIdType, Payment, Requisite, StringExt, TechCode};
+
+    #[test]
+    fn receipt_test() {
+        let payment = Payment::builder(required())
+            .with_additional_requisites([
+                Requisite::Sum("100000".to_max_size().unwrap()),
+                Requisite::TechCode(TechCode::HousingAndUtilites),
+                Requisite::PayerIdType(PayerIdType::Passport.as_str().into()),
+                Requisite::PersAcc("1234567".into()),
+            ])
+            .build();
+
+        let receipt = payment.receipt();
+
+        assert_eq!(
+            receipt.to_text(),
+            "Получатель: ООО «Три кита»
+Счёт: 40702810138250123017
+Банк получателя: ОАО \"БАНК\"
+БИК: 044525225
+Корр. счёт: 30101810400000000225
+Сумма: 1 000,00 руб.
+Категория платежа: Коммунальные услуги, ЖКХ (02)
+Вид ДУЛ плательщика: Паспорт гражданина Российской Федерации
+Номер лицевого счета плательщика в организации (в системе учета ПУ): 1234567
+"
+        );
+
+        let html = receipt.to_html();
+        assert!(html.starts_with("<table class=\"payment-receipt\">\n"));
+        assert!(html.contains("  <tr><th>Банк получателя</th><td>ОАО &quot;БАНК&quot;</td></tr>\n"));
+        assert!(html.ends_with("</table>\n"));
+    }
+}
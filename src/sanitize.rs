@@ -0,0 +1,374 @@
+//! Замена символов, которые нельзя записать в кодировке платежа.
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use crate::{CustomRequisites, Payment, PaymentEncoding, RawSegment, Requisite};
+
+/// Похожие символы: варианты замены в порядке предпочтения.
+const LOOKALIKES: [(char, &[&str]); 32] = [
+    ('«', &["\""]),
+    ('»', &["\""]),
+    ('„', &["«", "\""]),
+    ('“', &["«", "\""]),
+    ('”', &["»", "\""]),
+    ('‟', &["\""]),
+    ('‘', &["'"]),
+    ('’', &["'"]),
+    ('‚', &["'"]),
+    ('‛', &["'"]),
+    ('′', &["'"]),
+    ('″', &["\""]),
+    ('‐', &["-"]),
+    ('‑', &["-"]),
+    ('‒', &["–", "-"]),
+    ('–', &["-"]),
+    ('—', &["–", "-"]),
+    ('―', &["—", "-"]),
+    ('−', &["-"]),
+    ('\u{a0}', &[" "]),
+    ('\u{2002}', &[" "]),
+    ('\u{2003}', &[" "]),
+    ('\u{2007}', &[" "]),
+    ('\u{2009}', &[" "]),
+    ('\u{202f}', &[" "]),
+    ('…', &["..."]),
+    ('º', &["№", "°", "o"]),
+    ('°', &["º", "o"]),
+    ('•', &["·", "*"]),
+    ('×', &["x"]),
+    ('і', &["i"]),
+    ('І', &["I"]),
+];
+
+/// Транслитерация: замена символа последовательностью букв.
+///
+/// Заглавные буквы заменяются по строчным с сохранением регистра первой буквы.
+const TRANSLITERATION: [(char, &str); 48] = [
+    ('№', "N"),
+    ('є', "е"),
+    ('ї', "и"),
+    ('ґ', "г"),
+    ('ў', "у"),
+    ('á', "a"),
+    ('à', "a"),
+    ('â', "a"),
+    ('ä', "a"),
+    ('ã', "a"),
+    ('å', "a"),
+    ('ā', "a"),
+    ('æ', "ae"),
+    ('ç', "c"),
+    ('č', "c"),
+    ('ć', "c"),
+    ('é', "e"),
+    ('è', "e"),
+    ('ê', "e"),
+    ('ë', "e"),
+    ('ē', "e"),
+    ('ě', "e"),
+    ('í', "i"),
+    ('ì', "i"),
+    ('î', "i"),
+    ('ï', "i"),
+    ('ñ', "n"),
+    ('ń', "n"),
+    ('ó', "o"),
+    ('ò', "o"),
+    ('ô', "o"),
+    ('ö', "o"),
+    ('õ', "o"),
+    ('ø', "o"),
+    ('œ', "oe"),
+    ('ř', "r"),
+    ('š', "s"),
+    ('ś', "s"),
+    ('ß', "ss"),
+    ('ú', "u"),
+    ('ù', "u"),
+    ('û', "u"),
+    ('ü', "u"),
+    ('ý', "y"),
+    ('ž', "z"),
+    ('ł', "l"),
+    ('€', "EUR"),
+    ('™', "TM"),
+];
+
+/// Способ замены символа, который нельзя записать в кодировке платежа.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SanitizePolicy {
+    /// Замена похожим символом: типографские кавычки и тире, неразрывные пробелы.
+    Lookalikes,
+
+    /// Транслитерация: буквы с диакритикой, буквы украинского и белорусского алфавитов, знаки валют.
+    Transliterate,
+
+    /// Удаление символа.
+    Drop,
+}
+
+/// Правила замены символов, которые нельзя записать в кодировке платежа.
+///
+/// Способы замены применяются в порядке добавления. По умолчанию символы не заменяются.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Sanitizer {
+    policies: Vec<SanitizePolicy>,
+}
+
+impl Sanitizer {
+    /// Замена похожими символами, а затем транслитерация. Символы не удаляются.
+    pub fn lenient() -> Self {
+        Self::default()
+            .with_policy(SanitizePolicy::Lookalikes)
+            .with_policy(SanitizePolicy::Transliterate)
+    }
+
+    /// Добавление способа замены.
+    pub fn with_policy(mut self, policy: SanitizePolicy) -> Self {
+        if !self.policies.contains(&policy) {
+            self.policies.push(policy);
+        }
+        self
+    }
+
+    /// Замена символов строки, которые нельзя записать в кодировке `encoding`.
+    ///
+    /// Каждая замена добавляется в `changes`.
+    /// Если символ не удалось заменить, возвращается ошибка [`crate::Error::UnencodableChar`].
+    pub fn sanitize(
+        &self,
+        key: &str,
+        val: &str,
+        encoding: PaymentEncoding,
+        changes: &mut Vec<SanitizedChar>,
+    ) -> super::Result<String> {
+        let mut sanitized = String::with_capacity(val.len());
+
+        for ch in val.chars() {
+            if encoding.can_encode(ch) {
+                sanitized.push(ch);
+                continue;
+            }
+
+            let (policy, replacement) = self
+                .policies
+                .iter()
+                .find_map(|policy| replace(*policy, ch, encoding).map(|repl| (*policy, repl)))
+                .ok_or_else(|| super::Error::UnencodableChar {
+                    key: key.into(),
+                    ch,
+                })?;
+
+            sanitized.push_str(&replacement);
+            changes.push(SanitizedChar {
+                key: key.into(),
+                original: ch,
+                replacement: replacement.into(),
+                policy,
+            });
+        }
+
+        Ok(sanitized)
+    }
+}
+
+/// Замененный символ.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SanitizedChar {
+    key: Box<str>,
+    original: char,
+    replacement: Box<str>,
+    policy: SanitizePolicy,
+}
+
+impl SanitizedChar {
+    /// Ключ реквизита.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Исходный символ.
+    pub fn original(&self) -> char {
+        self.original
+    }
+
+    /// Замена, пустая при удалении символа.
+    pub fn replacement(&self) -> &str {
+        &self.replacement
+    }
+
+    /// Способ замены.
+    pub fn policy(&self) -> SanitizePolicy {
+        self.policy
+    }
+}
+
+impl<T: CustomRequisites> Payment<T> {
+    /// Замена символов реквизитов, которые нельзя записать в кодировке платежа.
+    ///
+    /// Возвращает список замен. Если какой-либо символ не удалось заменить,
+    /// возвращается ошибка [`crate::Error::UnencodableChar`], а платеж не изменяется.
+    pub fn sanitize(&mut self, sanitizer: &Sanitizer) -> super::Result<Vec<SanitizedChar>> {
+        let encoding = self.header.encoding;
+        let mut changes = Vec::new();
+
+        let mut requisites = Vec::new();
+        for (idx, requisite) in self.requisites.iter().enumerate() {
+            let before = changes.len();
+            let value =
+                sanitizer.sanitize(requisite.key(), requisite.value(), encoding, &mut changes)?;
+
+            if changes.len() != before {
+                requisites.push((idx, Requisite::try_from((requisite.key(), value.as_str()))?));
+            }
+        }
+
+        let mut layout = Vec::with_capacity(self.layout.len());
+        for segment in &self.layout {
+            layout.push(match segment {
+                RawSegment::Requisite => RawSegment::Requisite,
+                RawSegment::Unparsed(segment) => {
+                    let key = segment.split_once('=').map_or(&**segment, |(key, _)| key);
+                    let segment = sanitizer.sanitize(key, segment, encoding, &mut changes)?;
+                    RawSegment::Unparsed(segment.into_boxed_str())
+                }
+            });
+        }
+
+        for (idx, requisite) in requisites {
+            self.requisites[idx] = requisite;
+        }
+        self.layout = layout;
+
+        Ok(changes)
+    }
+}
+
+fn replace(policy: SanitizePolicy, ch: char, encoding: PaymentEncoding) -> Option<String> {
+    let encodable = |val: &str| val.chars().all(|ch| encoding.can_encode(ch));
+
+    match policy {
+        SanitizePolicy::Lookalikes => LOOKALIKES
+            .iter()
+            .find(|(lookalike, _)| *lookalike == ch)
+            .and_then(|(_, candidates)| candidates.iter().copied().find(|val| encodable(val)))
+            .map(Into::into),
+        SanitizePolicy::Transliterate => {
+            let lowercase = ch.to_lowercase().next()?;
+            let (_, latin) = TRANSLITERATION
+                .iter()
+                .find(|(translit, _)| *translit == lowercase)?;
+
+            let mut replacement = String::from(*latin);
+            if ch != lowercase {
+                let mut chars = latin.chars();
+                replacement = chars
+                    .next()
+                    .into_iter()
+                    .flat_map(char::to_uppercase)
+                    .chain(chars)
+                    .collect();
+            }
+
+            encodable(&replacement).then_some(replacement)
+        }
+        SanitizePolicy::Drop => Some(String::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fixtures::required, Error, ParserStrategy, Payment, PaymentEncoding, Requisite,
+        SanitizePolicy, Sanitizer, StringExt,
+    };
+
+    fn payment(purpose: &str) -> Payment {
+        Payment::builder(required())
+            .with_encdoing(PaymentEncoding::Koi8R)
+            .with_additional_requisites([Requisite::Purpose(purpose.to_max_size().unwrap())])
+            .build()
+    }
+
+    #[test]
+    fn unencodable_char_test() {
+        assert_eq!(
+            payment("Оплата по счету № 15").to_bytes(),
+            Err(Error::UnencodableChar {
+                key: "Name".into(),
+                ch: '«'
+            })
+        );
+
+        let mut payment = payment("Оплата 🎁");
+        payment.header_mut().set_encoding(PaymentEncoding::Win1251);
+        assert_eq!(
+            payment.to_bytes(),
+            Err(Error::UnencodableChar {
+                key: "Purpose".into(),
+                ch: '🎁'
+            })
+        );
+    }
+
+    #[test]
+    fn sanitize_test() {
+        let mut payment = payment("Оплата по счету № 15 — Café🎁");
+
+        let original = payment.clone();
+        assert_eq!(
+            payment.sanitize(&Sanitizer::lenient()),
+            Err(Error::UnencodableChar {
+                key: "Purpose".into(),
+                ch: '🎁'
+            })
+        );
+        assert_eq!(payment, original);
+
+        let changes = payment
+            .sanitize(&Sanitizer::lenient().with_policy(SanitizePolicy::Drop))
+            .unwrap();
+
+        assert_eq!(
+            changes
+                .iter()
+                .map(|change| (
+                    change.key(),
+                    change.original(),
+                    change.replacement(),
+                    change.policy()
+                ))
+                .collect::<alloc::vec::Vec<_>>(),
+            [
+                ("Name", '«', "\"", SanitizePolicy::Lookalikes),
+                ("Name", '»', "\"", SanitizePolicy::Lookalikes),
+                ("Purpose", '№', "N", SanitizePolicy::Transliterate),
+                ("Purpose", '—', "-", SanitizePolicy::Lookalikes),
+                ("Purpose", 'é', "e", SanitizePolicy::Transliterate),
+                ("Purpose", '🎁', "", SanitizePolicy::Drop),
+            ]
+        );
+
+        assert_eq!(payment.get("Name"), Some("ООО \"Три кита\""));
+        assert_eq!(payment.get("Purpose"), Some("Оплата по счету N 15 - Cafe"));
+        assert_eq!(
+            Payment::parser().parse_from_bytes(&payment.to_bytes().unwrap()),
+            Ok(payment)
+        );
+    }
+
+    #[test]
+    fn sanitize_win1251_test() {
+        let mut payment = payment("Оплата по счету № 15 — «Café»");
+        payment.header_mut().set_encoding(PaymentEncoding::Win1251);
+
+        let changes = payment.sanitize(&Sanitizer::lenient()).unwrap();
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(
+            payment.get("Purpose"),
+            Some("Оплата по счету № 15 — «Cafe»")
+        );
+        assert_eq!(payment.get("Name"), Some("ООО «Три кита»"));
+    }
+}
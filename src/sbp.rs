@@ -0,0 +1,251 @@
+//! Ссылки Системы быстрых платежей (`https://qr.nspk.ru/...`).
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use crate::{amount::parse_kopecks, CustomRequisites, Payment, Requisite, StringExt};
+
+const SBP_HOST: &str = "qr.nspk.ru";
+
+/// Ключи реквизитов, которые можно передать в ссылке СБП.
+const REPRESENTABLE_KEYS: [&str; 2] = ["Sum", "Purpose"];
+
+/// Ссылка СБП.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SbpLink {
+    /// Идентификатор QR-кода (торговой точки).
+    qr_id: Box<str>,
+
+    /// Параметры запроса в порядке следования.
+    params: Vec<(Box<str>, Box<str>)>,
+}
+
+impl SbpLink {
+    /// Проверка, что строка является ссылкой СБП.
+    pub fn is_sbp_link(val: &str) -> bool {
+        Self::parse(val).is_ok()
+    }
+
+    /// Разбор ссылки СБП.
+    pub fn parse(val: &str) -> super::Result<Self> {
+        let wrong_link = || super::Error::WrongSbpLink(val.into());
+
+        let link = val.trim();
+        let rest = link
+            .strip_prefix("https://")
+            .or_else(|| link.strip_prefix("http://"))
+            .ok_or_else(wrong_link)?;
+
+        let (host, rest) = rest.split_once('/').ok_or_else(wrong_link)?;
+        if !host.eq_ignore_ascii_case(SBP_HOST) {
+            return Err(wrong_link());
+        }
+
+        let rest = rest.split_once('#').map_or(rest, |(rest, _)| rest);
+        let (qr_id, query) = rest.split_once('?').unwrap_or((rest, ""));
+
+        if qr_id.is_empty() || !qr_id.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(wrong_link());
+        }
+
+        let params = query
+            .split('&')
+            .filter(|param| !param.is_empty())
+            .map(|param| {
+                let (key, value) = param.split_once('=').unwrap_or((param, ""));
+                Some((percent_decode(key)?, percent_decode(value)?))
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(wrong_link)?;
+
+        Ok(Self {
+            qr_id: qr_id.into(),
+            params,
+        })
+    }
+
+    /// Идентификатор QR-кода (торговой точки).
+    pub fn qr_id(&self) -> &str {
+        &self.qr_id
+    }
+
+    /// Значение параметра запроса.
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(k, _)| k.as_ref() == key)
+            .map(|(_, v)| v.as_ref())
+    }
+
+    /// Параметры запроса.
+    pub fn params(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params.iter().map(|(k, v)| (k.as_ref(), v.as_ref()))
+    }
+
+    /// Тип QR-кода: `01` - статический, `02` - динамический.
+    pub fn kind(&self) -> Option<&str> {
+        self.param("type")
+    }
+
+    /// Идентификатор банка получателя в СБП.
+    pub fn bank(&self) -> Option<&str> {
+        self.param("bank")
+    }
+
+    /// Сумма в копейках.
+    pub fn sum(&self) -> Option<&str> {
+        self.param("sum")
+    }
+
+    /// Валюта.
+    pub fn currency(&self) -> Option<&str> {
+        self.param("cur")
+    }
+
+    /// Назначение платежа.
+    pub fn purpose(&self) -> Option<&str> {
+        self.param("paymentPurpose")
+            .or_else(|| self.param("purpose"))
+    }
+
+    /// Реквизиты ГОСТ, которые можно получить из ссылки.
+    ///
+    /// Сумма переносится только для платежей в рублях.
+    pub fn requisites<T: CustomRequisites>(&self) -> super::Result<Vec<Requisite<T>>> {
+        let mut requisites = Vec::with_capacity(2);
+
+        if let Some(sum) = self
+            .sum()
+            .filter(|_| self.currency().is_none_or(|cur| cur == "RUB"))
+        {
+            requisites.push(Requisite::Sum(
+                sum.to_max_size()
+                    .filter(|_| parse_kopecks(sum).is_some())
+                    .ok_or_else(|| super::Error::WrongPair("Sum".into(), sum.into()))?,
+            ));
+        }
+
+        if let Some(purpose) = self.purpose() {
+            requisites.push(Requisite::Purpose(purpose.to_max_size().ok_or_else(
+                || super::Error::WrongPair("Purpose".into(), purpose.into()),
+            )?));
+        }
+
+        Ok(requisites)
+    }
+
+    /// Ключи реквизитов платежа, которые нельзя передать в ссылке СБП.
+    pub fn unrepresentable<T: CustomRequisites>(payment: &Payment<T>) -> Vec<&str> {
+        payment
+            .requisites()
+            .map(|requisite| requisite.key())
+            .filter(|key| !REPRESENTABLE_KEYS.contains(key))
+            .collect()
+    }
+}
+
+fn percent_decode(val: &str) -> Option<Box<str>> {
+    let mut bytes = Vec::with_capacity(val.len());
+    let mut iter = val.bytes();
+
+    while let Some(b) = iter.next() {
+        match b {
+            b'%' => {
+                let hi = (iter.next()? as char).to_digit(16)?;
+                let lo = (iter.next()? as char).to_digit(16)?;
+                bytes.push((hi * 16 + lo) as u8);
+            }
+            b'+' => bytes.push(b' '),
+            b => bytes.push(b),
+        }
+    }
+
+    String::from_utf8(bytes).ok().map(String::into_boxed_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{
+        fixtures::required, Error, NoCustomRequisites, Payment, Requisite, SbpLink, StringExt,
+    };
+
+    #[test]
+    fn sbp_link_test() {
+        let link = SbpLink::parse("https://qr.nspk.ru/AS1000670LSS7DN18SJQDNP4B05KLJL2?type=02&bank=100000000111&sum=10050&cur=RUB&crc=AB75&paymentPurpose=%D0%9E%D0%BF%D0%BB%D0%B0%D1%82%D0%B0+%D0%B7%D0%B0%D0%BA%D0%B0%D0%B7%D0%B0").unwrap();
+
+        assert_eq!(link.qr_id(), "AS1000670LSS7DN18SJQDNP4B05KLJL2");
+        assert_eq!(link.kind(), Some("02"));
+        assert_eq!(link.bank(), Some("100000000111"));
+        assert_eq!(link.sum(), Some("10050"));
+        assert_eq!(link.currency(), Some("RUB"));
+        assert_eq!(link.purpose(), Some("Оплата заказа"));
+        assert_eq!(link.param("crc"), Some("AB75"));
+
+        assert_eq!(
+            link.requisites::<NoCustomRequisites>(),
+            Ok(alloc::vec![
+                Requisite::Sum("10050".to_max_size().unwrap()),
+                Requisite::Purpose("Оплата заказа".to_max_size().unwrap()),
+            ])
+        );
+    }
+
+    #[test]
+    fn sbp_link_foreign_currency_test() {
+        let link = SbpLink::parse(
+            "https://qr.nspk.ru/AD100004BAL7227F9BNP6KNE007J9B3K?type=02&sum=100&cur=USD",
+        )
+        .unwrap();
+
+        assert_eq!(link.requisites::<NoCustomRequisites>(), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn wrong_sbp_link_test() {
+        assert!(SbpLink::is_sbp_link(
+            "https://QR.NSPK.RU/AD100004BAL7227F9BNP6KNE007J9B3K"
+        ));
+        assert!(!SbpLink::is_sbp_link(
+            "https://example.com/AD100004BAL7227F9BNP6KNE007J9B3K"
+        ));
+        assert!(!SbpLink::is_sbp_link("ST00012|Name=ООО «Три кита»"));
+        assert!(!SbpLink::is_sbp_link("https://qr.nspk.ru/?sum=100"));
+        assert!(!SbpLink::is_sbp_link("https://qr.nspk.ru/AD10?sum=%D0"));
+
+        let link = SbpLink::parse("https://qr.nspk.ru/AD10?sum=10,5").unwrap();
+        assert_eq!(
+            link.requisites::<NoCustomRequisites>(),
+            Err(Error::WrongPair("Sum".into(), "10,5".into()))
+        );
+
+        let link = SbpLink::parse("https://qr.nspk.ru/AD10?sum=&cur=RUB").unwrap();
+        assert_eq!(
+            link.requisites::<NoCustomRequisites>(),
+            Err(Error::WrongPair("Sum".into(), "".into()))
+        );
+    }
+
+    #[test]
+    fn sbp_unrepresentable_test() {
+        let payment = Payment::builder(required())
+            .with_additional_requisites([
+                Requisite::Sum("100000".to_max_size().unwrap()),
+                Requisite::Purpose("Оплата".to_max_size().unwrap()),
+                Requisite::PayeeINN("6200098765".to_max_size().unwrap()),
+            ])
+            .build();
+
+        assert_eq!(
+            SbpLink::unrepresentable(&payment),
+            [
+                "Name",
+                "PersonalAcc",
+                "BankName",
+                "BIC",
+                "CorrespAcc",
+                "PayeeINN"
+            ]
+        );
+    }
+}
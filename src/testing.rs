@@ -0,0 +1,234 @@
+//! Генераторы `proptest` для платежей.
+//!
+//! Генерируемые платежи всегда корректны: значения реквизитов кодируются во всех
+//! [`PaymentEncoding`] и не содержат разделителей из [`SEPARATORS`].
+
+use alloc::{format, string::String, vec::Vec};
+
+use proptest::{prelude::*, sample::select};
+
+use crate::{
+    CustomRequisites, ExactSizeString, LengthLimit, MaxSizeString, PayerIdType, Payment,
+    PaymentEncoding, RawRequisite, RequiredRequisite, Requisite, RequisiteRule, Snils, Version,
+};
+
+/// Разделители, используемые при генерации платежей.
+pub const SEPARATORS: [char; 6] = ['|', ';', '#', '^', '~', '!'];
+
+const REQUIRED_KEYS: [&str; 5] = ["Name", "PersonalAcc", "BankName", "BIC", "CorrespAcc"];
+
+const VALUE_CHARS: &str = "[а-яА-ЯёЁa-zA-Z0-9 .,\"=-]";
+
+/// Признак набора кодированных знаков.
+pub fn payment_encoding() -> impl Strategy<Value = PaymentEncoding> {
+    select(
+        &[
+            PaymentEncoding::Win1251,
+            PaymentEncoding::Utf8,
+            PaymentEncoding::Koi8R,
+        ][..],
+    )
+}
+
+/// Разделитель.
+pub fn separator() -> impl Strategy<Value = char> {
+    select(&SEPARATORS[..])
+}
+
+/// Текст длиной от `min` до `max` символов.
+pub fn text(min: usize, max: usize) -> impl Strategy<Value = String> {
+    proptest::string::string_regex(&format!("{}{{{},{}}}", VALUE_CHARS, min, max))
+        .expect("Неправильное регулярное выражение")
+}
+
+/// Строка из цифр длиной `N`.
+pub fn exact_digits<const N: usize>() -> impl Strategy<Value = ExactSizeString<N>> {
+    proptest::string::string_regex(&format!("[0-9]{{{}}}", N))
+        .expect("Неправильное регулярное выражение")
+        .prop_map(ExactSizeString::new_unchecked)
+}
+
+/// Строка длиной не более `N`.
+pub fn max_size_text<const N: usize>() -> impl Strategy<Value = MaxSizeString<N>> {
+    text(0, N).prop_map(MaxSizeString::new_unchecked)
+}
+
+/// Обязательные реквизиты.
+pub fn required_requisite() -> impl Strategy<Value = RequiredRequisite> {
+    (
+        max_size_text(),
+        exact_digits(),
+        max_size_text(),
+        exact_digits(),
+        max_size_text(),
+    )
+        .prop_map(
+            |(name, personal_acc, bank_name, bic, correstp_acc)| RequiredRequisite {
+                name,
+                personal_acc,
+                bank_name,
+                bic,
+                correstp_acc,
+            },
+        )
+}
+
+/// Значение реквизита, удовлетворяющее правилу версии [`Version::V0001`].
+pub fn requisite_value(rule: &RequisiteRule) -> BoxedStrategy<String> {
+    match (rule.key, rule.limit) {
+        ("TechCode", _) => (1..=15u8).prop_map(|code| format!("{:02}", code)).boxed(),
+        ("Phone", _) => "\\+79[0-9]{9}".boxed(),
+        ("PayerIdType", _) => select(&PayerIdType::ALL[..])
+            .prop_map(|id_type| id_type.as_str().into())
+            .boxed(),
+        ("PensAcc", _) => "00[2-9][0-9]{6}"
+            .prop_map(|number| format!("{}{:02}", number, Snils::checksum(number.as_bytes())))
+            .boxed(),
+        (_, LengthLimit::Unlimited) => text(0, 40).boxed(),
+        (_, LengthLimit::Max(max)) => text(0, max).boxed(),
+        (_, LengthLimit::Exact(exact)) => text(exact, exact).boxed(),
+    }
+}
+
+/// Дополнительный реквизит стандарта (любой, кроме обязательных и пользовательских).
+pub fn additional_requisite<T: CustomRequisites + core::fmt::Debug>(
+) -> impl Strategy<Value = Requisite<T>> {
+    let rules = Version::V0001
+        .rules()
+        .iter()
+        .filter(|rule| !REQUIRED_KEYS.contains(&rule.key))
+        .collect::<Vec<_>>();
+
+    select(rules).prop_flat_map(|rule| {
+        requisite_value(rule).prop_map(move |value| {
+            Requisite::try_from((rule.key, value.as_str()))
+                .expect("Сгенерировано неправильное значение реквизита")
+        })
+    })
+}
+
+/// Пользовательский реквизит, ключ которого не совпадает с ключами стандарта.
+pub fn raw_requisite() -> impl Strategy<Value = RawRequisite> {
+    ("X[a-zA-Z]{0,10}", text(0, 40)).prop_map(|(key, value)| RawRequisite::new(key, value))
+}
+
+/// Платеж с произвольными кодировкой, разделителем и дополнительными реквизитами.
+pub fn payment() -> impl Strategy<Value = Payment> {
+    payment_with(additional_requisite())
+}
+
+/// Платеж, в котором помимо реквизитов стандарта есть [`RawRequisite`].
+pub fn raw_payment() -> impl Strategy<Value = Payment<RawRequisite>> {
+    payment_with(prop_oneof![
+        additional_requisite(),
+        raw_requisite().prop_map(Requisite::Custom),
+    ])
+}
+
+/// Платеж с дополнительными реквизитами из переданного генератора.
+pub fn payment_with<T: CustomRequisites + core::fmt::Debug>(
+    requisite: impl Strategy<Value = Requisite<T>>,
+) -> impl Strategy<Value = Payment<T>> {
+    (
+        required_requisite(),
+        payment_encoding(),
+        separator(),
+        proptest::collection::vec(requisite, 0..16),
+    )
+        .prop_map(|(required, encoding, separator, requisites)| {
+            Payment::custom_builder(required)
+                .with_encdoing(encoding)
+                .with_separator(separator)
+                .with_additional_requisites(requisites)
+                .build()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use alloc::vec::Vec;
+
+    use crate::{Dialect, ParserStrategy, Payment, PaymentEncoding, RawRequisite};
+
+    proptest! {
+        #[test]
+        fn strict_parser_round_trip(payment in super::payment()) {
+            let bytes = payment.to_bytes().unwrap();
+            prop_assert_eq!(Payment::parser().parse_from_bytes(&bytes), Ok(payment));
+        }
+
+        #[test]
+        fn requisite_tolerance_parser_round_trip(payment in super::payment()) {
+            let bytes = payment.to_bytes().unwrap();
+            prop_assert_eq!(Payment::requisite_tolerance_parser().parse_from_bytes(&bytes), Ok(payment));
+        }
+
+        #[test]
+        fn loose_parser_round_trip(payment in super::payment()) {
+            let bytes = payment.to_bytes().unwrap();
+            prop_assert_eq!(Payment::loose_parser().parse_from_bytes(&bytes), Ok(payment));
+        }
+
+        #[test]
+        fn round_trip_parser_round_trip(payment in super::payment()) {
+            let bytes = payment.to_bytes().unwrap();
+            prop_assert_eq!(Payment::round_trip_parser().parse_from_bytes(&bytes), Ok(payment));
+        }
+
+        #[test]
+        fn raw_requisite_round_trip(payment in super::raw_payment()) {
+            let bytes = payment.to_bytes().unwrap();
+            prop_assert_eq!(&Payment::custom_parser().parse_from_bytes(&bytes), &Ok(payment.clone()));
+            prop_assert_eq!(Payment::round_trip_custom_parser().parse_from_bytes(&bytes), Ok(payment));
+        }
+
+        #[test]
+        fn parse_from_str_round_trip(mut payment in super::raw_payment()) {
+            payment.header_mut().set_encoding(PaymentEncoding::Utf8);
+            let raw = payment.to_utf8_lossy().unwrap();
+
+            prop_assert_eq!(&Payment::custom_parser().parse_from_str(&raw), &Ok(payment.clone()));
+            prop_assert_eq!(&Payment::requisite_tolerance_custom_parser().parse_from_str(&raw), &Ok(payment.clone()));
+            prop_assert_eq!(&Payment::loose_custom_parser().parse_from_str(&raw), &Ok(payment.clone()));
+            prop_assert_eq!(Payment::<RawRequisite>::round_trip_custom_parser().parse_from_str(&raw), Ok(payment));
+        }
+
+        #[test]
+        fn arbitrary_str_does_not_panic(tail in ".{0,64}", head in "(ST0001[0-9]?.?)?") {
+            let data = head + &tail;
+
+            let _ = Payment::parser().parse_from_str(&data);
+            let _ = Payment::requisite_tolerance_parser().parse_from_str(&data);
+            let _ = Payment::loose_parser().parse_from_str(&data);
+            let _ = Payment::requisite_tolerance_parser().with_dialect(Dialect::lenient()).parse_from_str(&data);
+            let _ = Payment::loose_parser().with_dialect(Dialect::lenient()).parse_from_str(&data);
+
+            if let Ok(payment) = Payment::<RawRequisite>::round_trip_custom_parser().parse_from_str(&data) {
+                prop_assert_eq!(payment.to_utf8_lossy(), Ok(data));
+            }
+        }
+
+        #[test]
+        fn arbitrary_bytes_does_not_panic(
+            head in proptest::collection::vec(any::<u8>(), 0..10),
+            tail in proptest::collection::vec(any::<u8>(), 0..64),
+            prefix in any::<bool>(),
+        ) {
+            let mut data = if prefix { b"ST0001".to_vec() } else { Vec::new() };
+            data.extend(head);
+            data.extend(tail);
+
+            let _ = Payment::parser().parse_from_bytes(&data);
+            let _ = Payment::requisite_tolerance_parser().parse_from_bytes(&data);
+            let _ = Payment::loose_parser().parse_from_bytes(&data);
+            let _ = Payment::requisite_tolerance_parser().with_dialect(Dialect::lenient()).parse_from_bytes(&data);
+            let _ = Payment::loose_parser().with_dialect(Dialect::lenient()).parse_from_bytes(&data);
+
+            if let Ok(payment) = Payment::<RawRequisite>::round_trip_custom_parser().parse_from_bytes(&data) {
+                prop_assert_eq!(payment.to_bytes(), Ok(data));
+            }
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! Правила для платежей на казначейские счета (УФК).
+
+use crate::{CustomRequisites, Payment};
+
+/// Префикс казначейского счета в `PersonalAcc`.
+const TREASURY_ACCOUNT_PREFIX: &str = "03";
+
+/// Префикс казначейского счета для учета поступлений в бюджеты.
+const REVENUE_ACCOUNT_PREFIX: &str = "03100";
+
+/// Префикс единого казначейского счета в `CorrespAcc`.
+const SINGLE_TREASURY_ACCOUNT_PREFIX: &str = "40102";
+
+/// Реквизиты, необходимые для платежей в бюджет на счета `03100`.
+const REVENUE_REQUISITES: [&str; 3] = ["CBC", "OKTMO", "UIN"];
+
+/// Реквизиты, необходимые для платежей на остальные казначейские счета.
+const BUDGET_RECIPIENT_REQUISITES: [&str; 1] = ["PersonalAccount"];
+
+impl<T: CustomRequisites> Payment<T> {
+    /// Платеж на казначейский счет.
+    ///
+    /// Казначейский счет определяется по `PersonalAcc` (`03...`) или по единому казначейскому счету в `CorrespAcc` (`40102...`).
+    pub fn is_treasury(&self) -> bool {
+        self.get("PersonalAcc")
+            .is_some_and(|acc| acc.starts_with(TREASURY_ACCOUNT_PREFIX))
+            || self
+                .get("CorrespAcc")
+                .is_some_and(|acc| acc.starts_with(SINGLE_TREASURY_ACCOUNT_PREFIX))
+    }
+
+    /// Проверка реквизитов платежа на казначейский счет.
+    ///
+    /// Казначейскому счету в `PersonalAcc` должен соответствовать единый казначейский счет в `CorrespAcc` и наоборот.
+    /// Для счетов `03100` (поступления в бюджет) необходимы `CBC`, `OKTMO` и `UIN`,
+    /// для остальных казначейских счетов - лицевой счет получателя `PersonalAccount`.
+    /// Платежи на другие счета не проверяются.
+    pub fn validate_treasury(&self) -> super::Result<()> {
+        if !self.is_treasury() {
+            return Ok(());
+        }
+
+        let personal_acc = self.get_required("PersonalAcc")?;
+        let corresp_acc = self.get_required("CorrespAcc")?;
+
+        if !personal_acc.starts_with(TREASURY_ACCOUNT_PREFIX)
+            || !corresp_acc.starts_with(SINGLE_TREASURY_ACCOUNT_PREFIX)
+        {
+            return Err(super::Error::WrongTreasuryAccounts {
+                personal_acc: personal_acc.into(),
+                corresp_acc: corresp_acc.into(),
+            });
+        }
+
+        let required = if personal_acc.starts_with(REVENUE_ACCOUNT_PREFIX) {
+            &REVENUE_REQUISITES[..]
+        } else {
+            &BUDGET_RECIPIENT_REQUISITES[..]
+        };
+
+        required
+            .iter()
+            .try_for_each(|key| self.get_required(key).map(|_| ()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fixtures::treasury_required, Error, NoCustomRequisites, Payment, RequiredRequisite,
+        Requisite, StringExt,
+    };
+
+    fn payment(
+        personal_acc: &str,
+        corresp_acc: &str,
+        requisites: impl IntoIterator<Item = Requisite<NoCustomRequisites>>,
+    ) -> Payment {
+        Payment::builder(RequiredRequisite {
+            personal_acc: personal_acc.to_exact_size().unwrap(),
+            correstp_acc: corresp_acc.to_max_size().unwrap(),
+            ..treasury_required()
+        })
+        .with_additional_requisites(requisites)
+        .build()
+    }
+
+    #[test]
+    fn treasury_revenue_test() {
+        let requisites = [
+            Requisite::CBC("18210102010011000110".to_max_size().unwrap()),
+            Requisite::OKTMO("45000000".to_max_size().unwrap()),
+            Requisite::UIN("0".into()),
+        ];
+        let payment = |count| {
+            payment(
+                "03100643000000017300",
+                "40102810545370000003",
+                requisites[..count].iter().cloned(),
+            )
+        };
+
+        assert!(payment(0).is_treasury());
+        assert_eq!(
+            payment(0).validate_treasury(),
+            Err(Error::MissingRequisite("CBC".into()))
+        );
+        assert_eq!(
+            payment(2).validate_treasury(),
+            Err(Error::MissingRequisite("UIN".into()))
+        );
+        assert_eq!(payment(3).validate_treasury(), Ok(()));
+    }
+
+    #[test]
+    fn treasury_budget_recipient_test() {
+        assert_eq!(
+            payment("03224643450000007300", "40102810545370000003", []).validate_treasury(),
+            Err(Error::MissingRequisite("PersonalAccount".into()))
+        );
+        assert_eq!(
+            payment(
+                "03224643450000007300",
+                "40102810545370000003",
+                [Requisite::PersonalAccount("20736У53380".into())]
+            )
+            .validate_treasury(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn treasury_accounts_mismatch_test() {
+        assert_eq!(
+            payment("03100643000000017300", "30101810400000000225", []).validate_treasury(),
+            Err(Error::WrongTreasuryAccounts {
+                personal_acc: "03100643000000017300".into(),
+                corresp_acc: "30101810400000000225".into(),
+            })
+        );
+        assert_eq!(
+            payment("40702810138250123017", "40102810545370000003", []).validate_treasury(),
+            Err(Error::WrongTreasuryAccounts {
+                personal_acc: "40702810138250123017".into(),
+                corresp_acc: "40102810545370000003".into(),
+            })
+        );
+
+        let payment = payment("40702810138250123017", "30101810400000000225", []);
+        assert!(!payment.is_treasury());
+        assert_eq!(payment.validate_treasury(), Ok(()));
+    }
+}
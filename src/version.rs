@@ -0,0 +1,242 @@
+use alloc::vec::Vec;
+
+use crate::{CustomRequisites, PaymentEncoding, Requisite, VERSION_0001_BYTES};
+
+/// Ограничение длины значения реквизита.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LengthLimit {
+    /// Без ограничения.
+    Unlimited,
+
+    /// Длина меньше или равна указанной.
+    Max(usize),
+
+    /// Длина равна указанной.
+    Exact(usize),
+}
+
+impl LengthLimit {
+    /// Проверка длины.
+    pub fn check(&self, len: usize) -> bool {
+        match self {
+            LengthLimit::Unlimited => true,
+            LengthLimit::Max(max) => len <= *max,
+            LengthLimit::Exact(exact) => len == *exact,
+        }
+    }
+}
+
+/// Единица измерения длины значения реквизита.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LengthUnit {
+    /// Символы.
+    #[default]
+    Chars,
+
+    /// Байты в кодировке платежа.
+    ///
+    /// Отличается от символов только для Utf-8, в котором кириллица занимает 2 байта.
+    EncodedBytes,
+}
+
+impl LengthUnit {
+    /// Длина значения в кодировке `encoding`.
+    ///
+    /// Возвращает `None`, если значение нельзя записать в кодировке.
+    pub fn measure(&self, val: &str, encoding: PaymentEncoding) -> Option<usize> {
+        match self {
+            LengthUnit::Chars => Some(val.chars().count()),
+            LengthUnit::EncodedBytes => encoding.encode(val).map(|bytes| bytes.len()),
+        }
+    }
+}
+
+/// Правило для реквизита в рамках версии стандарта.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RequisiteRule {
+    /// Ключ реквизита.
+    pub key: &'static str,
+
+    /// Ограничение длины значения.
+    pub limit: LengthLimit,
+}
+
+impl RequisiteRule {
+    /// Создание правила.
+    pub const fn new(key: &'static str, limit: LengthLimit) -> Self {
+        Self { key, limit }
+    }
+}
+
+/// Описание версии стандарта: идентификатор, набор реквизитов и ограничения их длины.
+///
+/// Ограничения версии проверяются дополнительно к ограничениям типов реквизитов.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Version {
+    id: [u8; 4],
+    rules: &'static [RequisiteRule],
+}
+
+impl Version {
+    /// Версия `0001`.
+    pub const V0001: Version = Version::new(VERSION_0001_BYTES, V0001_RULES);
+
+    /// Создание описания версии.
+    pub const fn new(id: [u8; 4], rules: &'static [RequisiteRule]) -> Self {
+        Self { id, rules }
+    }
+
+    /// Идентификатор версии.
+    pub fn id(&self) -> [u8; 4] {
+        self.id
+    }
+
+    /// Правила для реквизитов.
+    pub fn rules(&self) -> &'static [RequisiteRule] {
+        self.rules
+    }
+
+    /// Получение правила по ключу.
+    pub fn rule(&self, key: &str) -> Option<&'static RequisiteRule> {
+        self.rules.iter().find(|rule| rule.key == key)
+    }
+
+    /// Проверка реквизита по правилам версии.
+    ///
+    /// Пользовательские реквизиты не проверяются.
+    pub fn validate<T: CustomRequisites>(&self, requisite: &Requisite<T>) -> super::Result<()> {
+        self.validate_with(requisite, LengthUnit::Chars, PaymentEncoding::Utf8)
+    }
+
+    /// Проверка реквизита по правилам версии с длиной, измеренной в `unit` для кодировки `encoding`.
+    ///
+    /// Пользовательские реквизиты не проверяются.
+    pub fn validate_with<T: CustomRequisites>(
+        &self,
+        requisite: &Requisite<T>,
+        unit: LengthUnit,
+        encoding: PaymentEncoding,
+    ) -> super::Result<()> {
+        if matches!(requisite, Requisite::Custom(_)) {
+            return Ok(());
+        }
+
+        let rule =
+            self.rule(requisite.key())
+                .ok_or_else(|| super::Error::UnsupportedRequisite {
+                    key: requisite.key().into(),
+                    version: self.id,
+                })?;
+
+        let len = unit
+            .measure(requisite.value(), encoding)
+            .ok_or_else(|| encoding.unencodable_char(requisite.key(), requisite.value()))?;
+
+        if !rule.limit.check(len) {
+            return Err(super::Error::WrongPair(
+                requisite.key().into(),
+                requisite.value().into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Набор поддерживаемых версий стандарта.
+///
+/// По умолчанию содержит только [`Version::V0001`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionRegistry {
+    versions: Vec<Version>,
+}
+
+impl VersionRegistry {
+    /// Пустой набор версий.
+    pub fn empty() -> Self {
+        Self {
+            versions: Vec::new(),
+        }
+    }
+
+    /// Добавление версии.
+    ///
+    /// Если версия с таким идентификатором уже есть, то она заменяется.
+    pub fn with_version(mut self, version: Version) -> Self {
+        self.versions.retain(|v| v.id != version.id);
+        self.versions.push(version);
+        self
+    }
+
+    /// Получение версии по идентификатору.
+    pub fn get(&self, id: [u8; 4]) -> Option<&Version> {
+        self.versions.iter().find(|v| v.id == id)
+    }
+
+    /// Идентификаторы поддерживаемых версий.
+    pub fn ids(&self) -> impl Iterator<Item = [u8; 4]> + '_ {
+        self.versions.iter().map(|v| v.id)
+    }
+}
+
+impl Default for VersionRegistry {
+    fn default() -> Self {
+        Self::empty().with_version(Version::V0001)
+    }
+}
+
+const V0001_RULES: &[RequisiteRule] = &[
+    // Обязательные
+    RequisiteRule::new("Name", LengthLimit::Max(160)),
+    RequisiteRule::new("PersonalAcc", LengthLimit::Exact(20)),
+    RequisiteRule::new("BankName", LengthLimit::Max(45)),
+    RequisiteRule::new("BIC", LengthLimit::Exact(9)),
+    RequisiteRule::new("CorrespAcc", LengthLimit::Max(20)),
+    // Дополнительные
+    RequisiteRule::new("Sum", LengthLimit::Max(18)),
+    RequisiteRule::new("Purpose", LengthLimit::Max(210)),
+    RequisiteRule::new("PayeeINN", LengthLimit::Max(12)),
+    RequisiteRule::new("PayerINN", LengthLimit::Max(12)),
+    RequisiteRule::new("DrawerStatus", LengthLimit::Max(2)),
+    RequisiteRule::new("KPP", LengthLimit::Max(9)),
+    RequisiteRule::new("CBC", LengthLimit::Max(20)),
+    RequisiteRule::new("OKTMO", LengthLimit::Max(11)),
+    RequisiteRule::new("PaytReason", LengthLimit::Max(2)),
+    RequisiteRule::new("TaxPeriod", LengthLimit::Max(10)),
+    RequisiteRule::new("DocNo", LengthLimit::Max(15)),
+    RequisiteRule::new("DocDate", LengthLimit::Max(10)),
+    RequisiteRule::new("TaxPayKind", LengthLimit::Max(2)),
+    // Другие
+    RequisiteRule::new("LastName", LengthLimit::Unlimited),
+    RequisiteRule::new("FirstName", LengthLimit::Unlimited),
+    RequisiteRule::new("MiddleName", LengthLimit::Unlimited),
+    RequisiteRule::new("PayerAddress", LengthLimit::Unlimited),
+    RequisiteRule::new("PersonalAccount", LengthLimit::Unlimited),
+    RequisiteRule::new("DocIdx", LengthLimit::Unlimited),
+    RequisiteRule::new("PensAcc", LengthLimit::Unlimited),
+    RequisiteRule::new("Contract", LengthLimit::Unlimited),
+    RequisiteRule::new("PersAcc", LengthLimit::Unlimited),
+    RequisiteRule::new("Flat", LengthLimit::Unlimited),
+    RequisiteRule::new("Phone", LengthLimit::Unlimited),
+    RequisiteRule::new("PayerIdType", LengthLimit::Unlimited),
+    RequisiteRule::new("PayerIdNum", LengthLimit::Unlimited),
+    RequisiteRule::new("ChildFio", LengthLimit::Unlimited),
+    RequisiteRule::new("BirthDate", LengthLimit::Unlimited),
+    RequisiteRule::new("PaymTerm", LengthLimit::Unlimited),
+    RequisiteRule::new("PaymPeriod", LengthLimit::Unlimited),
+    RequisiteRule::new("Category", LengthLimit::Unlimited),
+    RequisiteRule::new("ServiceName", LengthLimit::Unlimited),
+    RequisiteRule::new("CounterId", LengthLimit::Unlimited),
+    RequisiteRule::new("CounterVal", LengthLimit::Unlimited),
+    RequisiteRule::new("QuittId", LengthLimit::Unlimited),
+    RequisiteRule::new("QuittDate", LengthLimit::Unlimited),
+    RequisiteRule::new("InstNum", LengthLimit::Unlimited),
+    RequisiteRule::new("ClassNum", LengthLimit::Unlimited),
+    RequisiteRule::new("SpecFio", LengthLimit::Unlimited),
+    RequisiteRule::new("AddAmount", LengthLimit::Unlimited),
+    RequisiteRule::new("RuleId", LengthLimit::Unlimited),
+    RequisiteRule::new("ExecId", LengthLimit::Unlimited),
+    RequisiteRule::new("RegType", LengthLimit::Unlimited),
+    RequisiteRule::new("UIN", LengthLimit::Unlimited),
+    RequisiteRule::new("TechCode", LengthLimit::Exact(2)),
+];